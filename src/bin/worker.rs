@@ -0,0 +1,141 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+use web_sys::{
+    DedicatedWorkerGlobalScope, MessageEvent, js_sys,
+    wasm_bindgen::{JsCast, JsValue, closure::Closure},
+};
+
+#[allow(dead_code)]
+#[path = "../fca"]
+mod fca {
+    pub mod closed_sets;
+    // the tests of the shared modules run on generated contexts
+    #[cfg(test)]
+    pub mod combine;
+    pub mod concepts;
+    pub mod derivation;
+    #[cfg(test)]
+    pub mod generators;
+}
+
+#[path = "../worker_protocol.rs"]
+mod worker_protocol;
+
+use crate::{
+    fca::{
+        closed_sets::{SetKind, visit_preclosures},
+        concepts::{Algorithm, same_concepts},
+    },
+    worker_protocol::{BATCH_SIZE, Diagram, Job, Message, decode_job, encode_message},
+};
+
+fn post(message: Message) {
+    let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+    scope
+        .post_message(&JsValue::from(encode_message(&message)))
+        .unwrap();
+}
+
+// The layout of rust-sugiyama, the concepts have to be in lectic order.
+fn diagram(concepts: &Vec<(BitSet, BitSet)>, context: &FormalContext<String>) -> Diagram {
+    let Some(graph) = odis::Graph::from_concepts(concepts, context) else {
+        return Diagram::default();
+    };
+    Diagram {
+        nodes: graph
+            .nodes
+            .iter()
+            .map(|node| (node.id, node.x as f64, node.y as f64, node.label.clone()))
+            .collect(),
+        edges: graph
+            .edges
+            .iter()
+            .map(|&(start, end)| (start as usize, end as usize))
+            .collect(),
+    }
+}
+
+fn run(job: Job, context: FormalContext<String>) {
+    let mut batch = Vec::new();
+    let lattice = matches!(job, Job::Lattice(_));
+
+    match job {
+        Job::Concepts(name) | Job::Lattice(name) => {
+            let algorithm = Algorithm::from_name(&name).unwrap_or(Algorithm::FCbO);
+            let mut concepts = Vec::new();
+            algorithm.enumerate(&context, &mut |concept| {
+                if lattice {
+                    concepts.push(concept.clone());
+                }
+                batch.push(concept);
+                if batch.len() == BATCH_SIZE {
+                    post(Message::Concepts(std::mem::take(&mut batch)));
+                }
+            });
+            post(Message::Concepts(batch));
+
+            if lattice {
+                // sorted like the page sorts the concepts it received, so the nodes index them
+                context.sort_lectic_order(&mut concepts);
+                post(Message::Diagram(diagram(&concepts, &context)));
+            }
+        }
+        Job::Basis => {
+            // the canonical basis, one implication per pseudo-intent
+            visit_preclosures(&context, &mut |step| {
+                if step.kind == SetKind::PseudoIntent {
+                    batch.push((step.set, step.closure));
+                    if batch.len() == BATCH_SIZE {
+                        post(Message::Implications(std::mem::take(&mut batch)));
+                    }
+                }
+            });
+            post(Message::Implications(batch));
+        }
+        Job::Compare => {
+            let mut reference = None;
+            let mut timings = Vec::new();
+            for algorithm in Algorithm::ALL {
+                let start = js_sys::Date::now();
+                let concepts = algorithm.compute(&context);
+                let milliseconds = js_sys::Date::now() - start;
+                let reference = reference.get_or_insert_with(|| concepts.clone());
+                timings.push((
+                    algorithm.name().to_string(),
+                    concepts.len(),
+                    milliseconds,
+                    same_concepts(reference, &concepts),
+                ));
+            }
+            post(Message::Timings(timings));
+        }
+    }
+
+    post(Message::Done);
+}
+
+fn main() {
+    console_error_panic_hook::set_once();
+
+    let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |ev: MessageEvent| {
+        let Some(text) = ev.data().as_string() else {
+            return;
+        };
+        let Some((job, context)) = decode_job(&text) else {
+            post(Message::Error("Unknown job.".to_string()));
+            return;
+        };
+
+        if let Ok(context) = FormalContext::<String>::from(context.as_bytes()) {
+            run(job, context);
+        } else {
+            post(Message::Error(
+                "The context could not be parsed.".to_string(),
+            ));
+        }
+    });
+
+    let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+    scope.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    onmessage.forget();
+}
@@ -0,0 +1,109 @@
+use leptos::{either::Either, logging, prelude::*};
+
+use odis::FormalContext;
+use web_sys::{MessageEvent, Worker, wasm_bindgen::closure::Closure};
+
+use crate::{
+    components::download::to_burmeister,
+    fca::concepts::Algorithm,
+    js_fn,
+    worker_protocol::{Job, Message, decode_message, encode_job},
+};
+
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub algorithm: Algorithm,
+    pub concepts: usize,
+    pub milliseconds: f64,
+    pub agrees: bool,
+}
+
+#[component]
+pub fn AlgorithmComparisonComp(context: RwSignal<FormalContext<String>>) -> impl IntoView {
+    let measurements: RwSignal<Option<Vec<Measurement>>> = RwSignal::new(None);
+
+    // the algorithms run in their own worker, independent of the concept computation
+    let worker = StoredValue::new_local(None::<(Worker, Closure<dyn FnMut(MessageEvent)>)>);
+    let running = RwSignal::new(false);
+
+    let cancel = move || {
+        worker.update_value(|worker| {
+            if let Some((worker, _)) = worker.take() {
+                worker.terminate();
+            }
+        });
+        running.set(false);
+    };
+
+    let compare = move |_| {
+        cancel();
+        measurements.set(None);
+        running.set(true);
+
+        let message = encode_job(&Job::Compare, &to_burmeister(&context.get_untracked()));
+        worker.set_value(Some(js_fn::start_worker(
+            message,
+            move |text| match decode_message(&text) {
+                Some(Message::Timings(timings)) => {
+                    measurements.set(Some(
+                        timings
+                            .into_iter()
+                            .filter_map(|(name, concepts, milliseconds, agrees)| {
+                                Some(Measurement {
+                                    algorithm: Algorithm::from_name(&name)?,
+                                    concepts,
+                                    milliseconds,
+                                    agrees,
+                                })
+                            })
+                            .collect(),
+                    ));
+                }
+                Some(Message::Done) => running.set(false),
+                Some(Message::Error(error)) => {
+                    logging::log!("Worker error: {}", error);
+                    running.set(false);
+                }
+                _ => {}
+            },
+        )));
+    };
+
+    view! {
+        <button on:click=compare>"Compare Algorithms"</button>
+        {move || {
+            running.get().then(|| view! {
+                <span>" Comparing algorithms… "</span>
+                <button on:click=move |_| cancel()>"Cancel"</button>
+            })
+        }}
+        {move || {
+            if let Some(measurements) = measurements.get() {
+                Either::Left(view! {
+                    <table style:margin-top="10px" style:margin-bottom="10px">
+                        <tbody>
+                            <tr>
+                                <th style:padding-right="15px">"Algorithm"</th>
+                                <th style:padding-right="15px">"Concepts"</th>
+                                <th style:padding-right="15px">"Time (ms)"</th>
+                                <th>"Same concept set"</th>
+                            </tr>
+                            {measurements.into_iter().map(|measurement| {
+                                view! {
+                                    <tr>
+                                        <td>{measurement.algorithm.name()}</td>
+                                        <td>{measurement.concepts}</td>
+                                        <td>{format!("{:.2}", measurement.milliseconds)}</td>
+                                        <td>{if measurement.agrees { "yes" } else { "no" }}</td>
+                                    </tr>
+                                }
+                            }).collect_view()}
+                        </tbody>
+                    </table>
+                })
+            } else {
+                Either::Right(view! {<br/>})
+            }
+        }}
+    }
+}
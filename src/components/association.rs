@@ -0,0 +1,146 @@
+use leptos::{either::Either, prelude::*};
+
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::{
+    fca::{
+        association::{Rule, all_rules, luxenburger_basis, to_csv},
+        derivation::format_set,
+    },
+    js_fn,
+};
+
+const SHOWN_RULES: usize = 500;
+
+#[component]
+pub fn AssociationComp(
+    // the context the concepts were computed for
+    context: RwSignal<FormalContext<String>>,
+    concepts: RwSignal<Option<Vec<(BitSet, BitSet)>>>,
+    // set while concepts are still arriving from the worker
+    running: RwSignal<bool>,
+) -> impl IntoView {
+    let link: NodeRef<leptos::html::A> = NodeRef::new();
+
+    let luxenburger = RwSignal::new(true);
+    let min_support = RwSignal::new("10".to_string());
+    let min_confidence = RwSignal::new("50".to_string());
+    let sort = RwSignal::new("none".to_string());
+    let rules: RwSignal<Option<Vec<Rule>>> = RwSignal::new(None);
+    let error = RwSignal::new("");
+
+    // rules of earlier concepts are dropped, the table drops the concepts with every edit
+    Effect::new(move |_| {
+        concepts.track();
+        rules.set(None);
+    });
+
+    let sorted = Memo::new(move |_| {
+        let mut rules = rules.get()?;
+        match sort.get().as_str() {
+            "support" => rules.sort_by(|a, b| b.support.total_cmp(&a.support)),
+            "confidence" => rules.sort_by(|a, b| b.confidence.total_cmp(&a.confidence)),
+            "lift" => rules.sort_by(|a, b| b.lift.total_cmp(&a.lift)),
+            _ => {}
+        }
+        Some(rules)
+    });
+
+    let calc_rules = move |_| {
+        if running.get() {
+            error.set("Wait until all concepts are found.");
+            return;
+        }
+        let Some(concepts) = concepts.get() else {
+            error.set("Compute the concepts first.");
+            return;
+        };
+        error.set("");
+        let context = context.get();
+
+        let min_support = min_support.get().parse::<f64>().unwrap_or(0.0) / 100.0;
+        let min_confidence = min_confidence.get().parse::<f64>().unwrap_or(0.0) / 100.0;
+
+        if luxenburger.get() {
+            rules.set(Some(luxenburger_basis(
+                &context,
+                &concepts,
+                min_support,
+                min_confidence,
+            )));
+        } else {
+            rules.set(Some(all_rules(
+                &context,
+                &concepts,
+                min_support,
+                min_confidence,
+            )));
+        }
+    };
+
+    view! {
+        <button on:click=calc_rules>"Compute Association Rules"</button>
+        <br/>
+        <select on:change=move |ev| luxenburger.set(event_target_value(&ev) == "luxenburger")>
+            <option value="luxenburger">"Luxenburger basis"</option>
+            <option value="all">"All rules"</option>
+        </select>
+        <br/>
+        <label>"Min. support (%): "</label>
+        <input type="number" min="0" max="100" style:width="60px" bind:value=min_support/>
+        <br/>
+        <label>"Min. confidence (%): "</label>
+        <input type="number" min="0" max="100" style:width="60px" bind:value=min_confidence/>
+        <br/>
+        <select on:change=move |ev| sort.set(event_target_value(&ev))>
+            <option value="none">"Unsorted"</option>
+            <option value="support">"Support (descending)"</option>
+            <option value="confidence">"Confidence (descending)"</option>
+            <option value="lift">"Lift (descending)"</option>
+        </select>
+        <button on:click=move |_| {
+            if let Some(rules) = sorted.get() {
+                js_fn::download(
+                    &link.get().unwrap(),
+                    "Association_rules.csv",
+                    to_csv(&rules, &context.get().attributes),
+                    "text/csv;charset=utf-8",
+                );
+            }
+        }>"Export"</button>
+        <a
+            node_ref=link
+            style="display: none"
+        />
+        <p style:color="red">{move || error.get()}</p>
+
+        {move || {
+            if let Some(rules) = sorted.get() {
+                let attributes = context.get().attributes;
+                Either::Left(view! {
+                    <p>{format!("The number of rules is: {}", rules.len())}</p>
+                    <ul style:max-height="300px" style:overflow-y="scroll">
+                        {rules.into_iter().take(SHOWN_RULES).enumerate().map(|(n, rule)| {
+                            view! {
+                                <li style:white-space="pre">
+                                    {format!(
+                                        "{}: {} ->\n      {}\n      supp {:.2}  conf {:.2}  lift {:.2}",
+                                        n + 1,
+                                        format_set(&rule.premise, &attributes),
+                                        format_set(&rule.conclusion, &attributes),
+                                        rule.support,
+                                        rule.confidence,
+                                        rule.lift,
+                                    )}
+                                </li>
+                            }
+                        }).collect_view()}
+                    </ul>
+                })
+            } else {
+                Either::Right(view! {<p>"..."</p>})
+            }
+        }}
+    }
+}
@@ -0,0 +1,113 @@
+use leptos::{either::Either, prelude::*};
+
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::fca::{
+    bases::{Basis, equivalent, statistics},
+    derivation::format_set,
+};
+
+// every basis with its implications
+type Comparison = Vec<(Basis, Vec<(BitSet, BitSet)>)>;
+
+#[component]
+pub fn BasesComp(context: RwSignal<FormalContext<String>>) -> impl IntoView {
+    let bases: RwSignal<Option<Comparison>> = RwSignal::new(None);
+    let shown = RwSignal::new(Basis::Canonical);
+
+    // the bases of an earlier context no longer match its attributes
+    Effect::new(move |_| {
+        context.track();
+        bases.set(None);
+    });
+
+    let compare = move |_| {
+        let context = context.get();
+        bases.set(Some(
+            Basis::ALL
+                .into_iter()
+                .map(|basis| (basis, basis.compute(&context)))
+                .collect(),
+        ));
+    };
+
+    view! {
+        <button on:click=compare>"Compare Implication Bases"</button>
+        {move || {
+            if let Some(bases) = bases.get() {
+                // the bases are dropped with every edit, so they fit this context
+                let attributes = context.get_untracked().attributes;
+                let rows: Vec<_> = bases
+                    .iter()
+                    .map(|(basis, implications)| {
+                        let others = bases
+                            .iter()
+                            .filter(|(other, other_implications)| {
+                                other != basis && equivalent(implications, other_implications)
+                            })
+                            .map(|(other, _)| other.name())
+                            .collect::<Vec<_>>();
+                        (*basis, statistics(implications), others)
+                    })
+                    .collect();
+                let implications = bases
+                    .iter()
+                    .find(|(basis, _)| *basis == shown.get())
+                    .map(|(_, implications)| implications.clone())
+                    .unwrap_or_default();
+
+                Either::Left(view! {
+                    <table style:margin-top="10px">
+                        <tbody>
+                            <tr>
+                                <th style:padding-right="15px">"Basis"</th>
+                                <th style:padding-right="15px">"Implications"</th>
+                                <th style:padding-right="15px">"Premises"</th>
+                                <th style:padding-right="15px">"Size"</th>
+                                <th>"Equivalent to"</th>
+                            </tr>
+                            {rows.into_iter().map(|(basis, (count, premises, size), others)| {
+                                view! {
+                                    <tr>
+                                        <td>{basis.name()}</td>
+                                        <td>{count}</td>
+                                        <td>{premises}</td>
+                                        <td>{size}</td>
+                                        <td>{others.join(", ")}</td>
+                                    </tr>
+                                }
+                            }).collect_view()}
+                        </tbody>
+                    </table>
+
+                    <select on:change=move |ev| {
+                        if let Some(basis) = Basis::from_name(&event_target_value(&ev)) {
+                            shown.set(basis);
+                        }
+                    }>
+                        {Basis::ALL.into_iter().map(|basis| {
+                            view! {
+                                <option value=basis.name() selected=basis == shown.get_untracked()>{basis.name()}</option>
+                            }
+                        }).collect_view()}
+                    </select>
+                    <ul style:max-height="300px" style:overflow-y="scroll">
+                        {implications.into_iter().enumerate().map(|(n, (premise, conclusion))| {
+                            view! {
+                                <li style:white-space="pre">{format!(
+                                    "{}: {} ->\n      {}",
+                                    n + 1,
+                                    format_set(&premise, &attributes),
+                                    format_set(&conclusion, &attributes),
+                                )}</li>
+                            }
+                        }).collect_view()}
+                    </ul>
+                })
+            } else {
+                Either::Right(view! {<br/>})
+            }
+        }}
+    }
+}
@@ -17,6 +17,13 @@ pub fn CheckboxComp(
             on:click=move |_| {
                 position.update(|pos| *pos = (row, column));
 
+                // clicking an unknown incidence decides it
+                if table.with(|table| table.unknown.contains(&(row, column))) {
+                    table.update(|table| {
+                        table.unknown.remove(&(row, column));
+                    });
+                }
+
                 if !table.get().boxes.get(&(row, column)).unwrap().get() {
                     context.update(|context| {
                         context.incidence.insert((row, column));
@@ -31,6 +38,14 @@ pub fn CheckboxComp(
                     });
                 }
             }
+            prop:indeterminate=move || table.with(|table| table.unknown.contains(&(row, column)))
+            style:outline=move || {
+                if table.with(|table| table.unknown.contains(&(row, column))) {
+                    "2px dashed orange"
+                } else {
+                    "none"
+                }
+            }
             type="checkbox" bind:checked=*table.get_untracked().boxes.get(&(row, column)).unwrap()
         />
     }
@@ -0,0 +1,111 @@
+use leptos::{either::Either, prelude::*};
+
+use odis::FormalContext;
+
+use crate::fca::{
+    closed_sets::{SUBSET_LIMIT, SetKind, Step, all_subsets, preclosure_steps},
+    derivation::format_set,
+};
+
+#[component]
+pub fn ClosedSetsComp(context: RwSignal<FormalContext<String>>) -> impl IntoView {
+    let steps: RwSignal<Option<Vec<Step>>> = RwSignal::new(None);
+    // number of steps revealed so far
+    let shown = RwSignal::new(0);
+    let show_subsets = RwSignal::new(false);
+
+    // the steps of an earlier context no longer match its attributes
+    Effect::new(move |_| {
+        context.track();
+        steps.set(None);
+    });
+
+    view! {
+        <button on:click=move |_| {
+            steps.set(Some(preclosure_steps(&context.get())));
+            shown.set(0);
+        }>"Pseudo-Intents and Closed Sets"</button>
+
+        {move || {
+            if let Some(all_steps) = steps.get() {
+                // the steps are dropped with every edit, so they fit this context
+                let attributes = context.get_untracked().attributes;
+                let total = all_steps.len();
+                let pseudo_intents = all_steps
+                    .iter()
+                    .filter(|step| step.kind == SetKind::PseudoIntent)
+                    .count();
+
+                Either::Left(view! {
+                    <p>{format!(
+                        "{} intents and {} pseudo-intents in lectic order:",
+                        total - pseudo_intents,
+                        pseudo_intents,
+                    )}</p>
+                    <button on:click=move |_| shown.update(|n| *n = (*n + 1).min(total))>"Step"</button>
+                    <button on:click=move |_| shown.set(total)>"Show all"</button>
+                    <button on:click=move |_| shown.set(0)>"Reset"</button>
+                    <p>{move || format!("Step {} of {}", shown.get(), total)}</p>
+
+                    <ol style:max-height="300px" style:overflow-y="scroll">
+                        {move || {
+                            all_steps.iter().take(shown.get()).map(|step| {
+                                let text = if step.kind == SetKind::PseudoIntent {
+                                    format!(
+                                        "{}  pseudo-intent\n      -> {}",
+                                        format_set(&step.set, &attributes),
+                                        format_set(&step.closure, &attributes),
+                                    )
+                                } else {
+                                    format!("{}  intent", format_set(&step.set, &attributes))
+                                };
+                                view! {
+                                    <li
+                                        style:white-space="pre"
+                                        style:font-weight=if step.kind == SetKind::PseudoIntent { "bold" } else { "normal" }
+                                    >{text}</li>
+                                }
+                            }).collect_view()
+                        }}
+                    </ol>
+                })
+            } else {
+                Either::Right(view! {<br/>})
+            }
+        }}
+
+        {move || {
+            let n = context.get_untracked().attributes.len();
+            if steps.get().is_some() && n <= SUBSET_LIMIT {
+                Either::Left(view! {
+                    <label>
+                        <input type="checkbox" bind:checked=show_subsets/>
+                        {format!("List all {} subsets", 1u32 << n)}
+                    </label>
+                })
+            } else {
+                Either::Right(())
+            }
+        }}
+        {move || {
+            match (show_subsets.get(), steps.get()) {
+                (true, Some(steps)) if context.get_untracked().attributes.len() <= SUBSET_LIMIT => {
+                    let context = context.get_untracked();
+                    let attributes = context.attributes.clone();
+                    Either::Left(view! {
+                        <ul style:max-height="300px" style:overflow-y="scroll">
+                            {all_subsets(&context, &steps).into_iter().map(|(set, kind)| {
+                                view! {
+                                    <li style:white-space="pre">
+                                        {format!("{}  {}", format_set(&set, &attributes), kind.name())}
+                                    </li>
+                                }
+                            }).collect_view()}
+                        </ul>
+                    })
+                }
+                _ => Either::Right(()),
+            }
+        }}
+    }
+}
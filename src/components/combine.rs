@@ -0,0 +1,250 @@
+use leptos::{either::Either, html::Input, prelude::*, task::spawn_local};
+
+use bit_set::BitSet;
+use odis::FormalContext;
+use web_sys::SubmitEvent;
+
+use crate::{fca::combine, js_fn};
+
+#[component]
+pub fn CombineComp(
+    context: RwSignal<FormalContext<String>>,
+    working_context: RwSignal<Option<FormalContext<String>>>,
+) -> impl IntoView {
+    let show_dialog = RwSignal::new("none");
+    let input_element: NodeRef<Input> = NodeRef::new();
+
+    let second = RwSignal::new(None::<FormalContext<String>>);
+    let subposition = RwSignal::new(false);
+
+    // matching[n] is the partner in the second context of the n-th object (apposition)
+    // or attribute (subposition) of the current context
+    let matching: RwSignal<Vec<Option<usize>>> = RwSignal::new(Vec::new());
+    let unmatched: RwSignal<Vec<usize>> = RwSignal::new(Vec::new());
+    let candidates: RwSignal<Vec<usize>> = RwSignal::new(Vec::new());
+    let error = RwSignal::new("");
+    // names of the other kind that occur in both contexts
+    let clashing: RwSignal<Vec<String>> = RwSignal::new(Vec::new());
+
+    // the names that are put side by side, attributes for apposition
+    let joined_names = move |context: &FormalContext<String>| {
+        if subposition.get_untracked() {
+            context.objects.clone()
+        } else {
+            context.attributes.clone()
+        }
+    };
+
+    let names = move |context: &FormalContext<String>| {
+        if subposition.get_untracked() {
+            context.attributes.clone()
+        } else {
+            context.objects.clone()
+        }
+    };
+
+    let rematch = move || {
+        if let Some(second) = second.get_untracked() {
+            let result = combine::match_by_name(&names(&context.get_untracked()), &names(&second));
+            let partners: BitSet = result.iter().flatten().copied().collect();
+
+            unmatched.set((0..result.len()).filter(|n| result[*n].is_none()).collect());
+            candidates.set(
+                (0..names(&second).len())
+                    .filter(|n| !partners.contains(*n))
+                    .collect(),
+            );
+            matching.set(result);
+        }
+    };
+
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+
+        if input_element.get().unwrap().value().is_empty() {
+            error.set("No file selected yet.");
+            return;
+        }
+
+        let file = input_element
+            .get()
+            .unwrap()
+            .files()
+            .unwrap()
+            .item(0)
+            .unwrap();
+
+        spawn_local(async move {
+            let contents = js_fn::file_contents(file).await;
+            if let Ok(n) = FormalContext::<String>::from(contents.as_bytes()) {
+                second.set(Some(n));
+                error.set("");
+                clashing.set(Vec::new());
+                rematch();
+            } else {
+                second.set(None);
+                error.set("The file could not be parsed.");
+            }
+        });
+    };
+
+    let combine = move |rename: bool| {
+        let Some(mut second) = second.get() else {
+            error.set("Load a second context first.");
+            return;
+        };
+
+        let mut used = BitSet::new();
+        for partner in matching.get().iter().flatten() {
+            if !used.insert(*partner) {
+                error.set("Every name of the second context may only be matched once.");
+                return;
+            }
+        }
+
+        let own = joined_names(&context.get());
+        let other = joined_names(&second);
+        let clashes = combine::clashes(&own, &other);
+        if !clashes.is_empty() && !rename {
+            clashing.set(clashes);
+            return;
+        }
+        for (n, name) in combine::suffixed(&own, &other).into_iter().enumerate() {
+            if subposition.get() {
+                second.change_object_name(name, n);
+            } else {
+                second.change_attribute_name(name, n);
+            }
+        }
+        clashing.set(Vec::new());
+
+        let result = if subposition.get() {
+            combine::subposition(&context.get(), &second, &matching.get())
+        } else {
+            combine::apposition(&context.get(), &second, &matching.get())
+        };
+
+        show_dialog.set("none");
+        working_context.set(Some(result));
+    };
+
+    view! {
+        <button
+            style:margin-left="20px"
+            on:click=move |_| {
+                rematch();
+                show_dialog.set("block");
+        }>"Combine Contexts"</button>
+
+        <div
+            style:opacity="0.6"
+            style:background-color="#ccc"
+            style:position="fixed"
+            style:width="100%"
+            style:height="100%"
+            style:top="0px"
+            style:left="0px"
+            style:z-index="10"
+            style:display=show_dialog
+        />
+
+        <div
+            style:position="absolute"
+            style:top="50%"
+            style:left="50%"
+            style:transform="translate(-50%, -50%)"
+            style:background="white"
+            style:border="thin solid black"
+            style:z-index="11"
+            style:display=show_dialog
+        >
+            <div
+                style:margin="10px"
+            >
+                <p>"Load a second context:"</p>
+                <form on:submit=on_submit>
+                    <input type="file" node_ref=input_element/>
+                    <input type="submit" value="Load"/>
+                </form>
+
+                <p>
+                    <select on:change=move |ev| {
+                        subposition.set(event_target_value(&ev) == "subposition");
+                        clashing.set(Vec::new());
+                        rematch();
+                    }>
+                        <option value="apposition">"Apposition (same objects)"</option>
+                        <option value="subposition">"Subposition (same attributes)"</option>
+                    </select>
+                </p>
+
+                {move || {
+                    if let Some(second) = second.get() {
+                        let own = names(&context.get());
+                        let other = names(&second);
+                        let candidates = candidates.get();
+
+                        Either::Left(view! {
+                            <p>{format!(
+                                "{} of {} names matched.",
+                                own.len() - unmatched.get().len(),
+                                own.len(),
+                            )}</p>
+                            <table>
+                                <tbody>
+                                    {unmatched.get().into_iter().map(|n| {
+                                        view! {
+                                            <tr>
+                                                <td>{own[n].clone()}</td>
+                                                <td>
+                                                    <select on:change=move |ev| {
+                                                        let value = event_target_value(&ev);
+                                                        matching.update(|matching| matching[n] = value.parse().ok());
+                                                    }>
+                                                        <option value="">"(no partner)"</option>
+                                                        {candidates.iter().map(|m| {
+                                                            view! {
+                                                                <option value=m.to_string()>{other[*m].clone()}</option>
+                                                            }
+                                                        }).collect_view()}
+                                                    </select>
+                                                </td>
+                                            </tr>
+                                        }
+                                    }).collect_view()}
+                                </tbody>
+                            </table>
+                            <p>"Names of the second context without partner are appended."</p>
+                        })
+                    } else {
+                        Either::Right(view! {<p>"..."</p>})
+                    }
+                }}
+
+                <p style:color="red">{move || error.get()}</p>
+                {move || {
+                    let clashes = clashing.get();
+                    if clashes.is_empty() {
+                        Either::Left(())
+                    } else {
+                        Either::Right(view! {
+                            <p style:color="red">{format!(
+                                "Both contexts contain the {} {}. Rename them in the second context or add a suffix.",
+                                if subposition.get() { "objects" } else { "attributes" },
+                                clashes.join(", "),
+                            )}</p>
+                            <button on:click=move |_| combine(true)>"Combine with Suffixes"</button>
+                        })
+                    }
+                }}
+
+                <button on:click=move |_| combine(false)>"Combine"</button>
+                <button on:click=move |_| {
+                    show_dialog.set("none");
+                    error.set("");
+                    clashing.set(Vec::new());
+                }>"Cancel"</button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,237 @@
+use leptos::{either::Either, prelude::*};
+
+use bit_set::BitSet;
+use odis::FormalContext;
+use std::cmp::Reverse;
+
+use crate::fca::{
+    concepts::{iceberg, min_count},
+    derivation::format_set,
+    measures::{Measure, all_measures},
+};
+
+const PAGE_SIZE: usize = 50;
+
+#[component]
+pub fn ConceptListComp(
+    // the context the concepts were computed for
+    context: RwSignal<FormalContext<String>>,
+    concepts: RwSignal<Option<Vec<(BitSet, BitSet)>>>,
+    min_support: RwSignal<f64>,
+    // set while concepts are still arriving from the worker
+    running: RwSignal<bool>,
+) -> impl IntoView {
+    let search = RwSignal::new(String::new());
+    let attribute = RwSignal::new(String::new());
+    let min_extent = RwSignal::new("0".to_string());
+    let sort = RwSignal::new("lectic".to_string());
+    let page = RwSignal::new(0);
+    let show_measures = RwSignal::new(false);
+
+    let measures_needed =
+        Memo::new(move |_| show_measures.get() || Measure::from_name(&sort.get()).is_some());
+    // computed once all concepts are there, not for every batch
+    let measures = Memo::new(move |_| {
+        if !measures_needed.get() || running.get() {
+            return None;
+        }
+        concepts
+            .get()
+            .map(|concepts| all_measures(&context.get(), &concepts))
+    });
+
+    // positions in lectic order of the concepts passing the filters, in display order
+    let filtered = Memo::new(move |_| {
+        let Some(concepts) = concepts.get() else {
+            return Vec::new();
+        };
+        let context = context.get();
+
+        let search = search.get().to_lowercase();
+        let attribute = attribute.get().parse::<usize>().ok();
+        let min_extent = min_extent
+            .get()
+            .parse::<usize>()
+            .unwrap_or(0)
+            .max(min_count(context.objects.len(), min_support.get()));
+
+        let matches = |set: &BitSet, names: &[String]| {
+            set.iter()
+                .any(|n| names[n].to_lowercase().contains(&search))
+        };
+
+        let mut result: Vec<usize> = (0..concepts.len())
+            .filter(|n| {
+                let (extent, intent) = &concepts[*n];
+                extent.len() >= min_extent
+                    && attribute.is_none_or(|m| intent.contains(m))
+                    && (search.is_empty()
+                        || matches(extent, &context.objects)
+                        || matches(intent, &context.attributes))
+            })
+            .collect();
+
+        match sort.get().as_str() {
+            "extent-desc" => result.sort_by_key(|n| Reverse(concepts[*n].0.len())),
+            "extent-asc" => result.sort_by_key(|n| concepts[*n].0.len()),
+            "intent-desc" => result.sort_by_key(|n| Reverse(concepts[*n].1.len())),
+            "intent-asc" => result.sort_by_key(|n| concepts[*n].1.len()),
+            other => {
+                if let (Some(measure), Some(measures)) = (Measure::from_name(other), measures.get())
+                {
+                    let value =
+                        |n: &usize| measures.get(*n).map(|x| measure.value(x)).unwrap_or(0.0);
+                    result.sort_by(|a, b| value(b).total_cmp(&value(a)));
+                }
+            }
+        }
+        result
+    });
+
+    let pages = move || filtered.get().len().div_ceil(PAGE_SIZE).max(1);
+    let current_page = move || page.get().min(pages() - 1);
+
+    view! {
+        {move || {
+            if let Some(n) = concepts.get() {
+                let frequent = iceberg(&n, context.get().objects.len(), min_support.get()).len();
+                Either::Left(view! {
+                    <p>{if frequent == n.len() {
+                        format!("The number of concepts is: {}", n.len())
+                    } else {
+                        format!(
+                            "The number of concepts is: {} ({} with support ≥ {:.0}%)",
+                            n.len(),
+                            frequent,
+                            min_support.get() * 100.0,
+                        )
+                    }}</p>
+                })
+            } else {
+                Either::Right(view! {<p>"..."</p>})
+            }
+        }}
+
+        // outside the closure above so the controls keep their state while concepts arrive
+        <div style:display=move || {
+            if concepts.with(Option::is_some) { "block" } else { "none" }
+        }>
+            <input type="text" placeholder="Search names..." bind:value=search/>
+            <br/>
+            <select
+                prop:value=move || attribute.get()
+                on:change=move |ev| attribute.set(event_target_value(&ev))
+            >
+                <option value="">"Any attribute"</option>
+                {move || context.get().attributes.into_iter().enumerate().map(|(m, name)| {
+                    view! {
+                        <option value=m.to_string()>{format!("Contains {}", name)}</option>
+                    }
+                }).collect_view()}
+            </select>
+            <br/>
+            <label>"Extent size ≥ "</label>
+            <input type="number" min="0" style:width="60px" bind:value=min_extent/>
+            <br/>
+            <select
+                prop:value=move || sort.get()
+                on:change=move |ev| sort.set(event_target_value(&ev))
+            >
+                <option value="lectic">"Lectic order"</option>
+                <option value="extent-desc">"Extent size (descending)"</option>
+                <option value="extent-asc">"Extent size (ascending)"</option>
+                <option value="intent-desc">"Intent size (descending)"</option>
+                <option value="intent-asc">"Intent size (ascending)"</option>
+                {Measure::ALL.into_iter().map(|measure| {
+                    view! {
+                        <option value=measure.name()>{format!("{} (descending)", measure.name())}</option>
+                    }
+                }).collect_view()}
+            </select>
+            <br/>
+            <label>
+                <input type="checkbox" bind:checked=show_measures/>
+                "Show measures"
+            </label>
+            {move || {
+                (measures_needed.get() && running.get())
+                    .then_some(" (measures follow once all concepts are found)")
+            }}
+
+            <p>
+                <button on:click=move |_| page.set(current_page().saturating_sub(1))>"<"</button>
+                {move || format!(
+                    " Page {} of {} ({} matching) ",
+                    current_page() + 1,
+                    pages(),
+                    filtered.get().len(),
+                )}
+                <button on:click=move |_| page.set((current_page() + 1).min(pages() - 1))>">"</button>
+            </p>
+
+            <div style:max-height="300px" style:overflow-y="scroll">
+                <table>
+                    <thead>
+                        <tr>
+                            <th>"#"</th>
+                            <th>"Extent"</th>
+                            <th>"Intent"</th>
+                            {move || {
+                                measures.with(Option::is_some).then(|| {
+                                    Measure::ALL.into_iter().map(|measure| {
+                                        view! {<th>{measure.name()}</th>}
+                                    }).collect_view()
+                                })
+                            }}
+                        </tr>
+                    </thead>
+                    <tbody>
+                        <For
+                            each=move || {
+                                let page = filtered
+                                    .get()
+                                    .into_iter()
+                                    .skip(current_page() * PAGE_SIZE)
+                                    .take(PAGE_SIZE);
+                                concepts.with(|concepts| {
+                                    let concepts = concepts.as_deref().unwrap_or_default();
+                                    page.filter_map(|n| Some((n, concepts.get(n)?.clone())))
+                                        .collect::<Vec<(usize, (BitSet, BitSet))>>()
+                                })
+                            }
+                            key=|concept| concept.clone()
+                            children=move |(index, (extent, intent))| {
+                                let context = context.get_untracked();
+                                view! {
+                                    <tr>
+                                        <td>{index + 1}</td>
+                                        <td>{format_set(&extent, &context.objects)}</td>
+                                        <td>{format_set(&intent, &context.attributes)}</td>
+                                        {move || {
+                                            measures.get().and_then(|measures| measures.get(index).cloned()).map(|measures| {
+                                                Measure::ALL.into_iter().map(|measure| {
+                                                    let estimated = measures.estimated
+                                                        && matches!(
+                                                            measure,
+                                                            Measure::IntensionalStability | Measure::ExtensionalStability
+                                                        );
+                                                    view! {
+                                                        <td>{format!(
+                                                            "{:.2}{}",
+                                                            measure.value(&measures),
+                                                            if estimated { " (est.)" } else { "" },
+                                                        )}</td>
+                                                    }
+                                                }).collect_view()
+                                            })
+                                        }}
+                                    </tr>
+                                }
+                            }
+                        />
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
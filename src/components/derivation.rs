@@ -0,0 +1,136 @@
+use leptos::prelude::*;
+
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::fca::derivation::{attribute_derivation, format_set, object_derivation, object_hull};
+
+#[component]
+pub fn DerivationComp(
+    context: RwSignal<FormalContext<String>>,
+    highlight: RwSignal<(BitSet, BitSet)>,
+) -> impl IntoView {
+    let show_panel = RwSignal::new("none");
+    let of_objects = RwSignal::new(false);
+    let selection = RwSignal::new(BitSet::new());
+
+    // (selected set, derivation, closure, extent, intent)
+    let result = Memo::new(move |_| {
+        let context = context.get();
+        if of_objects.get() {
+            let set: BitSet = selection
+                .get()
+                .iter()
+                .filter(|g| *g < context.objects.len())
+                .collect();
+            let derivation = object_derivation(&context, &set);
+            let closure = object_hull(&context, &set);
+            (
+                set,
+                derivation.clone(),
+                closure.clone(),
+                closure,
+                derivation,
+            )
+        } else {
+            let set: BitSet = selection
+                .get()
+                .iter()
+                .filter(|m| *m < context.attributes.len())
+                .collect();
+            let derivation = attribute_derivation(&context, &set);
+            let closure = context.index_attribute_hull(&set);
+            (
+                set,
+                derivation.clone(),
+                closure.clone(),
+                derivation,
+                closure,
+            )
+        }
+    });
+
+    Effect::new(move |_| {
+        if show_panel.get() == "block" {
+            let (_, _, _, extent, intent) = result.get();
+            highlight.set((extent, intent));
+        } else {
+            highlight.set((BitSet::new(), BitSet::new()));
+        }
+    });
+
+    let names = move |objects: bool| {
+        if objects {
+            context.get().objects
+        } else {
+            context.get().attributes
+        }
+    };
+
+    view! {
+        <button on:click=move |_| {
+            if show_panel.get() == "none" {
+                show_panel.set("block");
+            } else {
+                show_panel.set("none");
+            }
+        }>"Derivation Calculator"</button>
+
+        <div style:display=show_panel style:margin-top="10px">
+            <select on:change=move |ev| {
+                of_objects.set(event_target_value(&ev) == "objects");
+                selection.set(BitSet::new());
+            }>
+                <option value="attributes">"Set of attributes"</option>
+                <option value="objects">"Set of objects"</option>
+            </select>
+
+            <div style:max-height="150px" style:overflow-y="scroll" style:margin-top="10px">
+                {move || {
+                    names(of_objects.get()).into_iter().enumerate().map(|(n, name)| {
+                        view! {
+                            <label style:display="inline-block" style:margin-right="15px">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || selection.get().contains(n)
+                                    on:change=move |_| selection.update(|set| {
+                                        if !set.remove(n) {
+                                            set.insert(n);
+                                        }
+                                    })
+                                />
+                                {name}
+                            </label>
+                        }
+                    }).collect_view()
+                }}
+            </div>
+
+            <p style:white-space="pre">{move || {
+                let (set, derivation, closure, extent, intent) = result.get();
+                let (own, other) = (names(of_objects.get()), names(!of_objects.get()));
+
+                let missing = closure.difference(&set).collect::<BitSet>();
+                let kind = if of_objects.get() { "extent" } else { "intent" };
+
+                let mut text = format!("Derivation:  {}\n", format_set(&derivation, &other));
+                text.push_str(&format!("Closure:     {}\n", format_set(&closure, &own)));
+                text.push_str(&format!(
+                    "Concept:     ({}, {})\n",
+                    format_set(&extent, &context.get().objects),
+                    format_set(&intent, &context.get().attributes),
+                ));
+                if missing.is_empty() {
+                    text.push_str(&format!("The set is already an {}.", kind));
+                } else {
+                    text.push_str(&format!(
+                        "The set is not an {}, missing: {}",
+                        kind,
+                        format_set(&missing, &own),
+                    ));
+                }
+                text
+            }}</p>
+        </div>
+    }
+}
@@ -1,60 +1,663 @@
-use leptos::{logging, prelude::*};
+use leptos::{logging, prelude::*, task::spawn_local};
+use std::time::Duration;
 
 use bit_set::BitSet;
-use odis::{self, FormalContext, algorithms::canonical_basis};
+use odis::{self, FormalContext};
+
+use crate::{
+    components::{
+        download::to_burmeister,
+        table::{Table, pop_attribute, pop_object, push_attribute, push_object, replace_context},
+    },
+    fca::{
+        bases::equivalent,
+        combine::{align, transpose},
+        exploration::{Exploration, State},
+        implications::{counterexamples, format_implication, parse_implications},
+        partial::{plus_context, possibly_refuted, transpose_unknown},
+        session::{self, Session},
+        transcript,
+    },
+    js_fn,
+};
+
+const STORAGE_KEY: &str = "odis-web-exploration";
+
+// (certain, unknown) positions of a row of `TriStateComp`s
+fn split_states(states: &[(usize, RwSignal<Option<bool>>)]) -> (BitSet, BitSet) {
+    let mut certain = BitSet::new();
+    let mut unknown = BitSet::new();
+    for (n, (_, state)) in states.iter().enumerate() {
+        match state.get() {
+            Some(true) => {
+                certain.insert(n);
+            }
+            Some(false) => {}
+            None => {
+                unknown.insert(n);
+            }
+        }
+    }
+    (certain, unknown)
+}
 
-use crate::components::table::Table;
+// A checkbox cycling through no, yes and unknown (`None`).
+#[component]
+fn TriStateComp(state: RwSignal<Option<bool>>) -> impl IntoView {
+    view! {
+        <input
+            type="checkbox"
+            prop:checked=move || state.get() == Some(true)
+            prop:indeterminate=move || state.get().is_none()
+            on:click=move |ev| {
+                let next = match state.get() {
+                    Some(false) => Some(true),
+                    Some(true) => None,
+                    None => Some(false),
+                };
+                state.set(next);
+                let input = event_target::<web_sys::HtmlInputElement>(&ev);
+                input.set_checked(next == Some(true));
+                input.set_indeterminate(next.is_none());
+            }
+        />
+    }
+}
 
 #[component]
 pub fn ExplorationComp(
     context: RwSignal<FormalContext<String>>,
     table: RwSignal<Table>,
     row_key: RwSignal<usize>,
+    column_key: RwSignal<usize>,
     object_names: RwSignal<Vec<NodeRef<leptos::html::Input>>>,
+    attribute_names: RwSignal<Vec<NodeRef<leptos::html::Input>>>,
+    canonical_base: RwSignal<Option<Vec<(BitSet, BitSet)>>>,
+    // a basis that is still arriving from the worker is incomplete
+    basis_running: RwSignal<bool>,
+    // the context the basis was computed for
+    basis_context: RwSignal<FormalContext<String>>,
 ) -> impl IntoView {
-    let show_question_1 = RwSignal::new("none");
-    let show_question_2 = RwSignal::new("none");
-    let show_finished = RwSignal::new("none");
-    let input_block = RwSignal::new("none");
-
-    let start_node = NodeRef::new();
-
-    let basis: RwSignal<Vec<(BitSet, BitSet)>> = RwSignal::new(Vec::new());
-    let temp_set: RwSignal<BitSet> = RwSignal::new(BitSet::new());
-    let temp_set_hull = RwSignal::new(BitSet::new());
-
-    let break_while_2 = RwSignal::new(false);
-
-    let new_object: NodeRef<leptos::html::Input> = NodeRef::new();
-    let checkboxes: RwSignal<Vec<(usize, RwSignal<bool>)>> = RwSignal::new(Vec::new());
+    // `None` while no exploration is running
+    let exploration: RwSignal<Option<Exploration>> = RwSignal::new(None);
+    let state =
+        move || exploration.with(|exploration| exploration.as_ref().map(|e| e.state.clone()));
+    let display = move |shown: bool| if shown { "block" } else { "none" };
+
+    // the implication asked about
+    let question = move || {
+        exploration
+            .with(|exploration| exploration.as_ref().and_then(Exploration::question))
+            .unwrap_or_default()
+    };
+    let background = move || {
+        exploration
+            .with(|exploration| exploration.as_ref().map(|e| e.background.clone()))
+            .unwrap_or_default()
+    };
+    let basis = move || {
+        exploration
+            .with(|exploration| exploration.as_ref().map(|e| e.basis.clone()))
+            .unwrap_or_default()
+    };
+    let history = move || {
+        exploration
+            .with(|exploration| exploration.as_ref().map(|e| e.history.clone()))
+            .unwrap_or_default()
+    };
+
+    let new_object = RwSignal::new(String::new());
+    // `None` marks an attribute the expert does not know
+    let checkboxes: RwSignal<Vec<(usize, RwSignal<Option<bool>>)>> = RwSignal::new(Vec::new());
     let box_key: RwSignal<usize> = RwSignal::new(0);
 
-    view! {
-        <button
-            node_ref=start_node
-            on:click=move |_| {
-                input_block.set("block");
-
-                while temp_set.get() != (0..context.get().attributes.len()).collect() {
-
-                    *temp_set_hull.write() = context.get().index_attribute_hull(&temp_set.get());
-
-                    if temp_set.get() != temp_set_hull.get() && !break_while_2.get() {
-
-                        show_question_1.set("block");
-                        break;
-
-                    } else {
-
-                        break_while_2.set(false);
-                        *temp_set.write() = canonical_basis::next_preclosure(&context.get(), &basis.get(), &temp_set.get());
-
+    // implications known in advance, kept apart from the confirmed ones in `basis`
+    let background_text = RwSignal::new(String::new());
+    let background_error = RwSignal::new(String::new());
+    let background_file: NodeRef<leptos::html::Input> = NodeRef::new();
+    // object exploration asks about object implications and takes attributes as counterexamples
+    let dual = RwSignal::new(false);
+    // unknown incidences are assumed to hold while exploring
+    let explored = move || {
+        let plus = table.with(|table| plus_context(&context.get(), &table.unknown));
+        if dual.get() { transpose(&plus) } else { plus }
+    };
+    let explored_certain = move || {
+        if dual.get() {
+            transpose(&context.get())
+        } else {
+            context.get()
+        }
+    };
+    let explored_unknown = move || {
+        table.with(|table| {
+            if dual.get() {
+                transpose_unknown(&table.unknown)
+            } else {
+                table.unknown.clone()
+            }
+        })
+    };
+    let counterexample = move || split_states(&checkboxes.get());
+    let kind = move || if dual.get() { "attribute" } else { "object" };
+    let other_kind = move || if dual.get() { "object" } else { "attribute" };
+
+    let start = move |_| {
+        let context = explored();
+        let started = parse_implications(&background_text.get(), &context.attributes)
+            .and_then(|background| Exploration::new(&context, background));
+        match started {
+            Ok(started) => {
+                background_error.set(String::new());
+                exploration.set(Some(started));
+            }
+            Err(error) => background_error.set(error),
+        }
+    };
+
+    let accept = move || {
+        let context = explored();
+        exploration.update(|exploration| {
+            if let Some(exploration) = exploration {
+                exploration.accept(&context, js_fn::timestamp());
+            }
+        });
+    };
+
+    let submit = move |name: String, attribute_set: BitSet, unknown: BitSet| {
+        if dual.get() {
+            push_attribute(
+                context,
+                table,
+                column_key,
+                attribute_names,
+                name.clone(),
+                &attribute_set,
+            );
+            let m = context.get().attributes.len() - 1;
+            table.update(|table| table.unknown.extend(unknown.iter().map(|g| (g, m))));
+        } else {
+            push_object(
+                context,
+                table,
+                row_key,
+                object_names,
+                name.clone(),
+                &attribute_set,
+            );
+            let g = context.get().objects.len() - 1;
+            table.update(|table| table.unknown.extend(unknown.iter().map(|m| (g, m))));
+        }
+
+        let a = context.get().atomic_attribute_derivations;
+        let b = context.get().atomic_object_derivations;
+
+        logging::log!("Atomic attr: {:?}\n", a);
+        logging::log!("Atomic obj: {:?}\n", b);
+
+        let context = explored();
+        exploration.update(|exploration| {
+            if let Some(exploration) = exploration {
+                exploration.counterexample_added(
+                    &context,
+                    name,
+                    &attribute_set,
+                    &unknown,
+                    js_fn::timestamp(),
+                );
+            }
+        });
+    };
+
+    // answers every question from a reference context
+    let reference: RwSignal<Option<FormalContext<String>>> = RwSignal::new(None);
+    let reference_file: NodeRef<leptos::html::Input> = NodeRef::new();
+    let reference_error = RwSignal::new(String::new());
+    let oracle = RwSignal::new(false);
+    let delay = RwSignal::new("200".to_string());
+
+    let aligned_reference = move || {
+        reference.get().map(|reference| {
+            let reference = if dual.get() {
+                transpose(&reference)
+            } else {
+                reference
+            };
+            align(&reference, &explored().attributes)
+        })
+    };
+
+    let load_reference = move |_| {
+        let Some(file) = reference_file.get().unwrap().files().unwrap().item(0) else {
+            return;
+        };
+        spawn_local(async move {
+            let contents = js_fn::file_contents(file).await;
+            if let Ok(n) = FormalContext::<String>::from(contents.as_bytes()) {
+                reference.set(Some(n));
+                reference_error.set(String::new());
+            } else {
+                reference.set(None);
+                reference_error.set("The file could not be parsed.".to_string());
+            }
+        });
+    };
+
+    let oracle_step = move || {
+        let Some(Ok(reference)) = aligned_reference() else {
+            oracle.set(false);
+            return;
+        };
+        let (premise, conclusion) = question();
+        let refuting = counterexamples(&reference, &premise, &conclusion);
+
+        if let Some(g) = refuting.iter().next() {
+            let mut name = reference.objects[g].clone();
+            while explored().objects.contains(&name) {
+                name.push('\'');
+            }
+            let intent = reference.atomic_object_derivations[g].clone();
+            // the reference may contradict the background knowledge
+            let error = exploration.with_untracked(|exploration| {
+                exploration.as_ref().and_then(|exploration| {
+                    exploration.counterexample_error(&explored(), &name, &intent, &BitSet::new())
+                })
+            });
+            if let Some(error) = error {
+                oracle.set(false);
+                reference_error.set(format!(
+                    "The reference answer {} was refused. {}",
+                    name, error
+                ));
+                return;
+            }
+            exploration.update(|exploration| {
+                if let Some(exploration) = exploration {
+                    exploration.reject();
+                }
+            });
+            submit(name, intent, BitSet::new());
+        } else {
+            accept();
+        }
+    };
+
+    Effect::new(move |_| {
+        if oracle.get() && matches!(state(), Some(State::AskImplication { .. })) {
+            // answered questions identify the one this answer is meant for
+            let step = untrack(history).len();
+            set_timeout(
+                move || {
+                    if oracle.get_untracked()
+                        && matches!(untrack(state), Some(State::AskImplication { .. }))
+                        && untrack(history).len() == step
+                    {
+                        oracle_step();
                     }
+                },
+                Duration::from_millis(delay.get_untracked().parse().unwrap_or(200)),
+            );
+        }
+    });
+
+    // the basis of the reference is only computed for the finished dialog
+    let oracle_report = move || {
+        if state() != Some(State::Finished) {
+            return String::new();
+        }
+        let Some(Ok(reference)) = aligned_reference() else {
+            return String::new();
+        };
+        let reference_basis = reference.canonical_basis();
+        let accepted = history()
+            .iter()
+            .filter(|answer| answer.counterexample.is_none())
+            .count();
+
+        format!(
+            "{} questions, {} accepted, {} counterexamples.\nThe reference has {} implications in its canonical base, equivalent to the result: {}",
+            history().len(),
+            accepted,
+            history().len() - accepted,
+            reference_basis.len(),
+            if equivalent(&[background(), basis()].concat(), &reference_basis) {
+                "yes"
+            } else {
+                "no"
+            },
+        )
+    };
+
+    // Goes back to the question of `history[index]`, undoing it and all later answers.
+    let revert = move |index: usize| {
+        let Some(mut reverted) = exploration.get() else {
+            return;
+        };
+        let (num_objects, num_attributes) = reverted.revert(index);
+
+        while explored().objects.len() > num_objects {
+            if dual.get() {
+                pop_attribute(context, table, attribute_names);
+            } else {
+                pop_object(context, table, object_names);
+            }
+        }
+        // attributes added after the answer are taken back with it
+        while num_attributes.is_some_and(|n| explored().attributes.len() > n) {
+            if dual.get() {
+                pop_object(context, table, object_names);
+            } else {
+                pop_attribute(context, table, attribute_names);
+            }
+        }
+        // attributes kept from older sessions may change the question
+        reverted.advance(&explored());
+        exploration.set(Some(reverted));
+    };
+
+    // a new attribute, or object in object exploration, with its values for the explored objects
+    let adding_attribute = RwSignal::new(false);
+    let new_attribute = RwSignal::new(String::new());
+    let attribute_values: RwSignal<Vec<(usize, RwSignal<Option<bool>>)>> =
+        RwSignal::new(Vec::new());
+    let attribute_message = RwSignal::new(String::new());
+
+    let open_add_attribute = move |_| {
+        new_attribute.set(String::new());
+        attribute_values.set(Vec::new());
+        for _ in 0..explored().objects.len() {
+            attribute_values
+                .write()
+                .push((box_key.get(), RwSignal::new(Some(false))));
+            *box_key.write() += 1;
+        }
+        attribute_message.set(String::new());
+        adding_attribute.set(true);
+    };
+
+    let add_attribute = move |_| {
+        let name = new_attribute.get().trim().to_string();
+        if name.is_empty() {
+            attribute_message.set(format!("Enter a name for the new {}.", other_kind()));
+            return;
+        }
+        if explored().attributes.contains(&name) {
+            attribute_message.set(format!("The name {} is already taken.", name));
+            return;
+        }
+
+        let (extent, unknown) = split_states(&attribute_values.get());
+        if dual.get() {
+            push_object(context, table, row_key, object_names, name, &extent);
+            let g = context.get().objects.len() - 1;
+            table.update(|table| table.unknown.extend(unknown.iter().map(|m| (g, m))));
+        } else {
+            push_attribute(context, table, column_key, attribute_names, name, &extent);
+            let m = context.get().attributes.len() - 1;
+            table.update(|table| table.unknown.extend(unknown.iter().map(|g| (g, m))));
+        }
+
+        let context = explored();
+        exploration.update(|exploration| {
+            if let Some(exploration) = exploration {
+                exploration.attribute_added(&context);
+            }
+        });
+        attribute_message.set(String::new());
+        adding_attribute.set(false);
+    };
+
+    let session_link: NodeRef<leptos::html::A> = NodeRef::new();
+    let session_file: NodeRef<leptos::html::Input> = NodeRef::new();
+
+    let current_session = move || Session {
+        background: background(),
+        basis: basis(),
+        candidate: exploration
+            .with(|exploration| exploration.as_ref().map(|e| e.candidate.clone()))
+            .unwrap_or_default(),
+        history: history(),
+        dual: dual.get(),
+        unknown: table.with(|table| table.unknown.iter().copied().collect()),
+        context: to_burmeister(&context.get()),
+    };
+
+    let resume = move |text: &str| {
+        let session = match session::decode(text) {
+            Ok(session) => session,
+            Err(error) => {
+                background_error.set(error);
+                return;
+            }
+        };
+        let Ok(saved_context) = FormalContext::<String>::from(session.context.as_bytes()) else {
+            background_error.set("The context of the session could not be parsed.".to_string());
+            return;
+        };
+        if let Err(error) = session::check(&session, &saved_context) {
+            background_error.set(error);
+            return;
+        }
+
+        replace_context(
+            context,
+            table,
+            row_key,
+            column_key,
+            object_names,
+            attribute_names,
+            &saved_context,
+        );
+        table.update(|table| table.unknown = session.unknown.into_iter().collect());
+        dual.set(session.dual);
+        background_text.set(
+            session
+                .background
+                .iter()
+                .map(|implication| format_implication(implication, &explored().attributes))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        background_error.set(String::new());
+        exploration.set(Some(Exploration::resume(
+            &explored(),
+            session.background,
+            session.basis,
+            session.candidate,
+            session.history,
+        )));
+    };
+
+    let resume_from_file = move |_| {
+        let Some(file) = session_file.get().unwrap().files().unwrap().item(0) else {
+            return;
+        };
+        spawn_local(async move {
+            resume(&js_fn::file_contents(file).await);
+        });
+    };
+
+    let save_view = move || {
+        view! {
+            <button on:click=move |_| {
+                js_fn::download(
+                    &session_link.get().unwrap(),
+                    "exploration.session",
+                    session::encode(&current_session()),
+                    "text/plain",
+                );
+            }>"Save to File"</button>
+            <button on:click=move |_| {
+                match js_fn::store(STORAGE_KEY, &session::encode(&current_session())) {
+                    Ok(()) => background_error.set(String::new()),
+                    Err(error) => background_error.set(error),
                 }
-                if temp_set.get() == (0..context.get().attributes.len()).collect() {
-                    show_finished.set("block");
+            }>"Save in Browser"</button>
+            // the start panel with the other errors is hidden during the exploration
+            <span style:color="red">{move || background_error.get()}</span>
+        }
+    };
+
+    let transcript_view = move || {
+        let export =
+            move |format: fn(&Exploration, &FormalContext<String>, bool, f64) -> String,
+                  name: &str,
+                  mime: &str| {
+                if let Some(exploration) = exploration.get() {
+                    js_fn::download(
+                        &session_link.get().unwrap(),
+                        name,
+                        format(
+                            &exploration,
+                            &explored_certain(),
+                            dual.get(),
+                            js_fn::timestamp(),
+                        ),
+                        mime,
+                    );
                 }
-        }>"Start Exploration"</button>
+            };
+        view! {
+            <button on:click=move |_| {
+                export(transcript::markdown, "exploration.md", "text/markdown")
+            }>"Transcript (Markdown)"</button>
+            <button on:click=move |_| {
+                export(transcript::json, "exploration.json", "application/json")
+            }>"Transcript (JSON)"</button>
+        }
+    };
+
+    let history_view = move || {
+        let attributes = explored().attributes;
+        view! {
+            <p>"History:"</p>
+            <ol style:text-align="left" style:max-height="200px" style:overflow-y="auto">
+                {history().into_iter().enumerate().map(|(index, answer)| {
+                    let implication = format_implication(&(answer.premise, answer.conclusion), &attributes);
+                    let text = match answer.counterexample {
+                        Some(name) => format!("{}  rejected by {}", implication, name),
+                        None => format!("{}  accepted", implication),
+                    };
+                    view! {
+                        <li>
+                            {text}
+                            <button style:margin-left="5px" on:click=move |_| revert(index)>"Revert"</button>
+                        </li>
+                    }
+                }).collect_view()}
+            </ol>
+        }
+    };
+
+    let import_file = move |_| {
+        let Some(file) = background_file.get().unwrap().files().unwrap().item(0) else {
+            return;
+        };
+        spawn_local(async move {
+            let contents = js_fn::file_contents(file).await;
+            background_text.update(|text| {
+                if !text.is_empty() && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                text.push_str(&contents);
+            });
+        });
+    };
+
+    let import_canonical_base = move |_| {
+        let context = explored();
+        // the table drops the basis after an edit, until then it belongs to another context
+        let current = !dual.get()
+            && !basis_running.get()
+            && basis_context.with(|basis_context| basis_context.attributes == context.attributes);
+        let implications = match canonical_base.get() {
+            Some(implications) if current => implications,
+            _ => context.canonical_basis(),
+        };
+        background_text.set(
+            implications
+                .iter()
+                .map(|implication| format_implication(implication, &context.attributes))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+    };
+
+    // Why the ticked attributes do not refute the current question, if they do not.
+    let counterexample_error = move || -> Option<String> {
+        let context = explored();
+        let (intent, unknown) = counterexample();
+        exploration.with(|exploration| {
+            exploration.as_ref().and_then(|exploration| {
+                exploration.counterexample_error(&context, &new_object.get(), &intent, &unknown)
+            })
+        })
+    };
+
+    let implication_list = move |implications: Vec<(BitSet, BitSet)>| {
+        if implications.is_empty() {
+            view! {<p>"none"</p>}.into_any()
+        } else {
+            view! {
+                <ul style:text-align="left">
+                    {implications.iter().map(|implication| {
+                        view! {<li>{format_implication(implication, &explored().attributes)}</li>}
+                    }).collect_view()}
+                </ul>
+            }
+            .into_any()
+        }
+    };
+
+    view! {
+        <label>
+            <input type="checkbox" bind:checked=dual disabled=move || state().is_some()/>
+            "Explore objects instead of attributes"
+        </label>
+        <p>"Background knowledge (one implication per line):"</p>
+        <textarea
+            rows="5"
+            placeholder="a, b -> c"
+            prop:value=move || background_text.get()
+            on:input=move |ev| background_text.set(event_target_value(&ev))
+        />
+        <br/>
+        <input type="file" node_ref=background_file on:change=import_file/>
+        <button on:click=import_canonical_base>"Import Canonical Base"</button>
+        <p>{move || background_error.get()}</p>
+
+        <p>"Reference context for automatic answers:"</p>
+        <input type="file" node_ref=reference_file on:change=load_reference/>
+        <p>{move || {
+            match aligned_reference() {
+                Some(Err(error)) => error,
+                _ => reference_error.get(),
+            }
+        }}</p>
+        <label>
+            <input
+                type="checkbox"
+                bind:checked=oracle
+                disabled=move || !matches!(aligned_reference(), Some(Ok(_)))
+            />
+            "Answer from the reference, "
+        </label>
+        <input type="number" min="0" style:width="60px" bind:value=delay/>
+        " ms per answer"
+
+        <p>"Resume a saved exploration:"</p>
+        <input type="file" node_ref=session_file on:change=resume_from_file/>
+        <button on:click=move |_| {
+            match js_fn::load(STORAGE_KEY) {
+                Ok(Some(text)) => resume(&text),
+                Ok(None) => background_error.set("No exploration saved in the browser.".to_string()),
+                Err(error) => background_error.set(error),
+            }
+        }>"Resume from Browser"</button>
+        <a node_ref=session_link style="display: none"/>
+        <br/>
+
+        <button on:click=start>"Start Exploration"</button>
 
         <div
             style:opacity="0.6"
@@ -65,7 +668,7 @@ pub fn ExplorationComp(
             style:top="0px"
             style:left="0px"
             style:z-index="10"
-            style:display=input_block
+            style:display=move || display(state().is_some())
         />
 
         // Question: 1
@@ -77,67 +680,105 @@ pub fn ExplorationComp(
             style:background="white"
             style:border="thin solid black"
             style:z-index="11"
-            style:display=show_question_1
+            style:display=move || display(matches!(state(), Some(State::AskImplication { .. })))
         >
             <div
                 style:margin="10px"
             >
                 // Question text
-                <p>"Is the following implication valid?"</p>
+                <p>{move || {
+                    if dual.get() {
+                        "Does every attribute of the first objects also apply to the second ones?"
+                    } else {
+                        "Is the following implication valid?"
+                    }
+                }}</p>
                 <p>{move || {
                     let mut premise_string: Vec<String> = Vec::new();
-                    for index in &temp_set.get() {
-                        premise_string.push(context.get().attributes[index].to_string());
+                    for index in &question().0 {
+                        premise_string.push(explored().attributes[index].to_string());
                     }
                     format!("{:?}", premise_string)
                 }}</p>
                 <p>"=>"</p>
                 <p>{move || {
                     let mut conclusion_stirng: Vec<String> = Vec::new();
-                    for index in &temp_set_hull.get().difference(&temp_set.get()).collect::<BitSet>() {
-                        conclusion_stirng.push(context.get().attributes[index].to_string());
+                    let (premise, conclusion) = question();
+                    for index in &conclusion.difference(&premise).collect::<BitSet>() {
+                        conclusion_stirng.push(explored().attributes[index].to_string());
                     }
                     format!("{:?}", conclusion_stirng)
                 }}</p>
 
                 <button
-                    on:click=move |_| {
-                        basis.write().push((temp_set.get(), temp_set_hull.get()));
-                        break_while_2.set(true);
-                        show_question_1.set("none");
-                        start_node.get().unwrap().click();
-                }>"Yes"</button>
+                    on:click=move |_| accept()
+                >"Yes"</button>
 
                 <button
                     on:click=move |_| {
                         checkboxes.set(Vec::new());
-                        for n in 0..context.get().attributes.len() {
-                            if temp_set.get().contains(n) {
-                                checkboxes.write().push((box_key.get(), RwSignal::new(true)));
+                        new_object.set(String::new());
+                        for n in 0..explored().attributes.len() {
+                            if question().0.contains(n) {
+                                checkboxes.write().push((box_key.get(), RwSignal::new(Some(true))));
                                 *box_key.write() += 1;
                             } else {
-                                checkboxes.write().push((box_key.get(), RwSignal::new(false)));
+                                checkboxes.write().push((box_key.get(), RwSignal::new(Some(false))));
                                 *box_key.write() += 1;
                             }
                         }
 
-                        show_question_1.set("none");
-                        show_question_2.set("block");
+                        exploration.update(|exploration| {
+                            if let Some(exploration) = exploration {
+                                exploration.reject();
+                            }
+                        });
                 }>"No"</button>
 
                 <button
                     on:click=move |_| {
-                        input_block.set("none");
-                        show_question_1.set("none");
-
-                        basis.set(Vec::new());
-                        temp_set.set(BitSet::new());
-                        temp_set_hull.set(BitSet::new());
-
-                        break_while_2.set(false);
-
+                        exploration.set(None);
                         checkboxes.set(Vec::new());
                 }>"Stop exploration"</button>
+                <button
+                    style:display=move || if oracle.get() { "inline" } else { "none" }
+                    on:click=move |_| oracle.set(false)
+                >"Answer manually"</button>
+                <button on:click=open_add_attribute>
+                    {move || format!("Add {}", other_kind())}
+                </button>
+
+                <div style:display=move || display(adding_attribute.get())>
+                    <input
+                        type="text"
+                        placeholder=move || format!("Enter {} name...", other_kind())
+                        bind:value=new_attribute
+                    />
+                    <table style:background="#D3D3D3">
+                        <tbody>
+                            <For
+                                each=move || attribute_values.get().into_iter().enumerate()
+                                key=move |(_, value)| value.0
+                                children=move |(g, value)| {
+                                    view! {
+                                        <tr>
+                                            <td>{explored().objects.get(g).cloned().unwrap_or_default()}</td>
+                                            <td><TriStateComp state=value.1/></td>
+                                        </tr>
+                                    }
+                                }
+                            />
+                        </tbody>
+                    </table>
+                    <button on:click=add_attribute>"Add"</button>
+                    <button on:click=move |_| adding_attribute.set(false)>"Cancel"</button>
+                </div>
+                <p>{move || attribute_message.get()}</p>
+                <br/>
+                {save_view}
+                {transcript_view}
+
+                {history_view}
             </div>
         </div>
 
@@ -151,7 +792,7 @@ pub fn ExplorationComp(
             style:background="white"
             style:border="thin solid black"
             style:z-index="11"
-            style:display=show_question_2
+            style:display=move || display(matches!(state(), Some(State::AwaitCounterexample { .. })))
         >
             <div
                 style:margin="10px"
@@ -165,13 +806,13 @@ pub fn ExplorationComp(
                         <tr>
                             <td/>
                             <For
-                                each=move || (0..context.get().attributes.len())
+                                each=move || 0..explored().attributes.len()
                                 key=move |key| *key
                                 children=move |index| {
                                     view! {
                                         <td>
                                             <p>
-                                                {format!("{}", context.get().attributes[index].clone())}
+                                                {explored().attributes[index].clone()}
                                             </p>
                                         </td>
                                     }
@@ -183,11 +824,8 @@ pub fn ExplorationComp(
                             <td>
                                 <input
                                     type="text"
-                                    placeholder="Enter object name..."
-                                    on:change=move |_| {
-
-                                    }
-                                    node_ref=new_object
+                                    placeholder=move || format!("Enter {} name...", kind())
+                                    bind:value=new_object
                                 />
                             </td>
 
@@ -197,10 +835,7 @@ pub fn ExplorationComp(
                                 children=move |checkbox| {
                                     view! {
                                         <td>
-                                            <input
-                                                type="checkbox"
-                                                bind:checked=checkbox.1
-                                            />
+                                            <TriStateComp state=checkbox.1/>
                                         </td>
                                     }
                                 }
@@ -209,44 +844,21 @@ pub fn ExplorationComp(
                     </tbody>
                 </table>
 
-                <br/>
+                <p>"Click a box again to mark it as unknown."</p>
+                <p style:color="red">{move || counterexample_error().unwrap_or_default()}</p>
                 <button
+                    disabled=move || counterexample_error().is_some()
                     on:click=move |_| {
-                        let mut attribute_set = BitSet::new();
-                        for item in checkboxes.get().iter().enumerate() {
-                            if item.1.1.get() {
-                                attribute_set.insert(item.0);
-                            }
+                        if counterexample_error().is_some() {
+                            return;
                         }
 
-                        let num_obj = table.get().row_data.last().unwrap().1;
-
-                        table.update(|table| {
-                            table.row_data.push((row_key.get(), num_obj + 1))
-                        });
-                        row_key.update(|key| *key += 1);
-
-                        for n in 0..table.read_only().get().column_data.len() {
-                            table.update(|table| {
-                                table.boxes.insert((num_obj + 1, n), RwSignal::new(checkboxes.get()[n].1.get()));
-                            });
-                        };
-
-                        context.update(|context| {
-                            context.add_object(new_object.get().unwrap().value(), &attribute_set);
-                        });
-
-                        object_names.update(|list| list.push(NodeRef::new()));
-
-                        show_question_2.set("none");
-                        let a = context.get().atomic_attribute_derivations;
-                        let b = context.get().atomic_object_derivations;
-
-                        logging::log!("Atomic attr: {:?}\n", a);
-                        logging::log!("Atomic obj: {:?}\n", b);
-                        start_node.get().unwrap().click();
+                        let (attribute_set, unknown) = counterexample();
+                        submit(new_object.get().trim().to_string(), attribute_set, unknown);
                     }
                 >"Submit"</button>
+
+                {history_view}
             </div>
         </div>
 
@@ -258,25 +870,43 @@ pub fn ExplorationComp(
             style:background="white"
             style:border="thin solid black"
             style:z-index="11"
-            style:display=show_finished
+            style:display=move || display(state() == Some(State::Finished))
         >
             <div
                 style:margin="10px"
                 style:text-align="center"
             >
-                <p>"Attribute exploration complete."</p>
+                <p>{move || {
+                    if dual.get() {
+                        "Object exploration complete."
+                    } else {
+                        "Attribute exploration complete."
+                    }
+                }}</p>
+
+                <p style:white-space="pre-wrap">{oracle_report}</p>
+                <p>"Background knowledge:"</p>
+                {move || implication_list(background())}
+                <p>"Confirmed implications, certainly valid:"</p>
+                {move || {
+                    let (context, unknown) = (explored_certain(), explored_unknown());
+                    implication_list(basis().into_iter().filter(|(premise, conclusion)| {
+                        !possibly_refuted(&context, &unknown, premise, conclusion)
+                    }).collect())
+                }}
+                <p>"Confirmed implications, only possibly valid:"</p>
+                {move || {
+                    let (context, unknown) = (explored_certain(), explored_unknown());
+                    implication_list(basis().into_iter().filter(|(premise, conclusion)| {
+                        possibly_refuted(&context, &unknown, premise, conclusion)
+                    }).collect())
+                }}
+                {transcript_view}
+                {history_view}
 
                 <button
                     on:click=move |_| {
-                        show_finished.set("none");
-                        input_block.set("none");
-
-                        basis.set(Vec::new());
-                        temp_set.set(BitSet::new());
-                        temp_set_hull.set(BitSet::new());
-
-                        break_while_2.set(false);
-
+                        exploration.set(None);
                         checkboxes.set(Vec::new());
                     }
                 >"Exit"</button>
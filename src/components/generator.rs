@@ -0,0 +1,132 @@
+use leptos::{either::Either, prelude::*};
+
+use odis::FormalContext;
+
+use crate::fca::generators;
+
+#[component]
+pub fn GeneratorComp(working_context: RwSignal<Option<FormalContext<String>>>) -> impl IntoView {
+    let show_dialog = RwSignal::new("none");
+
+    let family = RwSignal::new("random".to_string());
+    let objects = RwSignal::new("10".to_string());
+    let attributes = RwSignal::new("10".to_string());
+    let density = RwSignal::new("0.3".to_string());
+    let seed = RwSignal::new("0".to_string());
+    let size = RwSignal::new("4".to_string());
+    let error = RwSignal::new("");
+
+    let known_concepts = move || {
+        let n: u32 = size.get().parse().unwrap_or(0);
+        match family.get().as_str() {
+            "contranominal" => format!("Number of concepts: 2^{} = {}", n, 2u128.saturating_pow(n)),
+            "ordinal" => format!("Number of concepts: {}", n),
+            "nominal" if n >= 2 => format!("Number of concepts: {}", n + 2),
+            "nominal" => format!("Number of concepts: {}", n),
+            _ => "".to_string(),
+        }
+    };
+
+    let generate = move |_| {
+        let result = if family.get() == "random" {
+            let (Ok(objects), Ok(attributes), Ok(density), Ok(seed)) = (
+                objects.get().parse::<usize>(),
+                attributes.get().parse::<usize>(),
+                density.get().parse::<f64>(),
+                seed.get().parse::<u64>(),
+            ) else {
+                error.set("Please enter valid numbers.");
+                return;
+            };
+            if objects == 0 || attributes == 0 || !(0.0..=1.0).contains(&density) {
+                error.set("At least one object and attribute, density between 0 and 1.");
+                return;
+            }
+            generators::random_context(objects, attributes, density, seed)
+        } else {
+            let Ok(n) = size.get().parse::<usize>() else {
+                error.set("Please enter a valid size.");
+                return;
+            };
+            if n == 0 {
+                error.set("The size must be at least 1.");
+                return;
+            }
+            match family.get().as_str() {
+                "contranominal" => generators::contranominal_scale(n),
+                "ordinal" => generators::ordinal_scale(n),
+                _ => generators::nominal_scale(n),
+            }
+        };
+
+        show_dialog.set("none");
+        error.set("");
+        working_context.set(Some(result));
+    };
+
+    view! {
+        <button
+            style:margin-left="20px"
+            on:click=move |_| show_dialog.set("block")
+        >"Generate Context"</button>
+
+        <div
+            style:opacity="0.6"
+            style:background-color="#ccc"
+            style:position="fixed"
+            style:width="100%"
+            style:height="100%"
+            style:top="0px"
+            style:left="0px"
+            style:z-index="10"
+            style:display=show_dialog
+        />
+
+        <div
+            style:position="absolute"
+            style:top="50%"
+            style:left="50%"
+            style:transform="translate(-50%, -50%)"
+            style:background="white"
+            style:border="thin solid black"
+            style:z-index="11"
+            style:display=show_dialog
+        >
+            <div
+                style:margin="10px"
+            >
+                <p>"Generate a context:"</p>
+                <select on:change=move |ev| family.set(event_target_value(&ev))>
+                    <option value="random">"Random context"</option>
+                    <option value="contranominal">"Contranominal scale"</option>
+                    <option value="ordinal">"Ordinal scale (chain)"</option>
+                    <option value="nominal">"Nominal scale"</option>
+                </select>
+
+                {move || {
+                    if family.get() == "random" {
+                        Either::Left(view! {
+                            <p>"Objects: " <input type="number" min="1" bind:value=objects/></p>
+                            <p>"Attributes: " <input type="number" min="1" bind:value=attributes/></p>
+                            <p>"Density: " <input type="number" min="0" max="1" step="0.05" bind:value=density/></p>
+                            <p>"Seed: " <input type="number" min="0" bind:value=seed/></p>
+                        })
+                    } else {
+                        Either::Right(view! {
+                            <p>"Size: " <input type="number" min="1" bind:value=size/></p>
+                            <p>{known_concepts}</p>
+                        })
+                    }
+                }}
+
+                <p style:color="red">{move || error.get()}</p>
+
+                <button on:click=generate>"Generate"</button>
+                <button on:click=move |_| {
+                    show_dialog.set("none");
+                    error.set("");
+                }>"Cancel"</button>
+            </div>
+        </div>
+    }
+}
@@ -3,18 +3,33 @@ use core::f64;
 use bit_set::BitSet;
 use leptos::{either::Either, prelude::*};
 use odis::FormalContext;
+use web_sys::{MouseEvent, WheelEvent};
 
-use crate::components::{
-    svg::{edge::EdgeComp, node::NodeComp},
-    svg_download::SvgDownloadComp,
+use crate::{
+    components::{
+        svg::{edge::EdgeComp, node::NodeComp},
+        svg_download::SvgDownloadComp,
+    },
+    fca::{
+        concepts::min_count,
+        layout::{self, Layout},
+        measures::{Measure, Measures, all_measures},
+    },
+    worker_protocol::Diagram,
 };
 
+// largest displayed size in pixels, bigger diagrams are shown zoomed out
+const VIEWPORT_LIMIT: f64 = 1000.0;
+const ZOOM_STEP: f64 = 1.2;
+// bounds of the zoom relative to the whole diagram
+const MIN_ZOOM: f64 = 0.2;
+const MAX_ZOOM: f64 = 20.0;
+const MINIMAP_WIDTH: f64 = 200.0;
+
 #[derive(Clone, Debug)]
 pub struct Node {
     pub id: usize,
     pub label: (Option<String>, Option<String>),
-    pub x: f64,
-    pub y: f64,
     pub x_signal: RwSignal<f64>,
     pub y_signal: RwSignal<f64>,
 }
@@ -28,34 +43,190 @@ pub struct Dimensions {
     pub font_size: u8,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Drag {
+    Node(usize),
+    // last pointer position in client coordinates
+    Pan(f64, f64),
+}
+
+// The part of the diagram that is shown, in diagram coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewBox {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ViewBox {
+    pub fn fit(dimensions: &Dimensions) -> Self {
+        ViewBox {
+            x: 0.0,
+            y: 0.0,
+            width: dimensions.width,
+            height: dimensions.height,
+        }
+    }
+
+    // pixels per diagram unit in a viewport of the given size, the svg default
+    // preserveAspectRatio="xMidYMid meet" keeps the aspect ratio and centres the view
+    pub fn scale(&self, viewport: (f64, f64)) -> f64 {
+        (viewport.0 / self.width).min(viewport.1 / self.height)
+    }
+
+    // diagram coordinates of a point given relative to the top left corner of the viewport
+    pub fn to_diagram(self, viewport: (f64, f64), point: (f64, f64)) -> (f64, f64) {
+        let scale = self.scale(viewport);
+        let left = (viewport.0 - self.width * scale) / 2.0;
+        let top = (viewport.1 - self.height * scale) / 2.0;
+        (
+            self.x + (point.0 - left) / scale,
+            self.y + (point.1 - top) / scale,
+        )
+    }
+
+    // `around` stays at the same place on the screen
+    pub fn zoomed(&self, factor: f64, around: (f64, f64)) -> Self {
+        ViewBox {
+            x: around.0 - (around.0 - self.x) * factor,
+            y: around.1 - (around.1 - self.y) * factor,
+            width: self.width * factor,
+            height: self.height * factor,
+        }
+    }
+
+    pub fn panned(&self, dx: f64, dy: f64) -> Self {
+        ViewBox {
+            x: self.x + dx,
+            y: self.y + dy,
+            ..*self
+        }
+    }
+
+    pub fn centred(&self, at: (f64, f64)) -> Self {
+        ViewBox {
+            x: at.0 - self.width / 2.0,
+            y: at.1 - self.height / 2.0,
+            ..*self
+        }
+    }
+
+    fn attribute(&self) -> String {
+        format!("{} {} {} {}", self.x, self.y, self.width, self.height)
+    }
+}
+
+// displayed size of the whole diagram in pixels
+fn viewport_size(dimensions: &Dimensions) -> (f64, f64) {
+    let shrink = (VIEWPORT_LIMIT / dimensions.width)
+        .min(VIEWPORT_LIMIT / dimensions.height)
+        .min(1.0);
+    (dimensions.width * shrink, dimensions.height * shrink)
+}
+
+// position of the mouse relative to the top left corner of `element` and the size of `element`
+fn locate(element: &web_sys::Element, ev: &MouseEvent) -> ((f64, f64), (f64, f64)) {
+    let rect = element.get_bounding_client_rect();
+    (
+        (
+            ev.client_x() as f64 - rect.x(),
+            ev.client_y() as f64 - rect.y(),
+        ),
+        (rect.width(), rect.height()),
+    )
+}
+
 impl Node {
     pub fn new(id: usize, label: (Option<String>, Option<String>), x: f64, y: f64) -> Self {
         Node {
             id,
             label,
-            x,
-            y,
             x_signal: RwSignal::new(x),
             y_signal: RwSignal::new(y),
         }
     }
 }
 
-#[component]
-pub fn GraphComp(concepts: Vec<(BitSet, BitSet)>, context: FormalContext<String>) -> impl IntoView {
-    let graph_option = odis::Graph::from_concepts(&concepts, &context);
+// value of the n-th concept scaled to [0, 1] over all concepts
+fn normalized(measures: &[Measures], measure: Measure, n: usize) -> f64 {
+    let values: Vec<f64> = measures.iter().map(|x| measure.value(x)).collect();
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
 
-    let mut graph = odis::Graph::new();
-    let mut error = " ";
+    if max - min > 0.0 {
+        (values.get(n).copied().unwrap_or(min) - min) / (max - min)
+    } else {
+        0.5
+    }
+}
 
-    if let Some(n) = graph_option {
-        graph = n;
+#[component]
+pub fn GraphComp(
+    concepts: Vec<(BitSet, BitSet)>,
+    context: FormalContext<String>,
+    // positions and labels from the worker
+    diagram: Diagram,
+    min_support: RwSignal<f64>,
+    // owned by the table so they are kept when the lattice is drawn again
+    layout: RwSignal<Layout>,
+    // attribute vectors of the additive layouts
+    vectors: RwSignal<Vec<f64>>,
+) -> impl IntoView {
+    let error = if diagram.nodes.is_empty() {
+        "Cannot draw concept lattice from singular concept."
     } else {
-        error = "Cannot draw concept lattice from singular concept.";
+        " "
+    };
+
+    let concept_count = concepts.len();
+    let object_count = context.objects.len();
+    let extent_sizes: Vec<usize> = concepts.iter().map(|(extent, _)| extent.len()).collect();
+    // the frequent concepts are shown, their covers are the edges of the whole lattice between them
+    let visible = Memo::new(move |_| {
+        let min_count = min_count(object_count, min_support.get());
+        (0..concept_count)
+            .filter(|n| extent_sizes[*n] >= min_count)
+            .collect::<BitSet>()
+    });
+    let intents: Vec<BitSet> = concepts.iter().map(|(_, intent)| intent.clone()).collect();
+    let intents = StoredValue::new(intents);
+    let mut layered = vec![(0.0, 0.0); concepts.len()];
+    for &(n, x, y, _) in &diagram.nodes {
+        layered[n] = (x, y);
     }
 
-    let width_node_ref: NodeRef<leptos::html::Input> = NodeRef::new();
-    let height_node_ref: NodeRef<leptos::html::Input> = NodeRef::new();
+    let edges = StoredValue::new(diagram.edges);
+
+    // vectors optimised for other attributes do not fit
+    if vectors.with_untracked(|vectors| vectors.len() != context.attributes.len()) {
+        vectors.set(layout::attribute_vectors(context.attributes.len()));
+    }
+    // unscaled positions of the concepts, starting at zero
+    let positions = Memo::new(move |_| {
+        intents.with_value(|intents| {
+            edges.with_value(|edges| {
+                layout::positions(layout.get(), intents, &vectors.get(), edges, &layered)
+            })
+        })
+    });
+
+    let optimise = move |_| {
+        let optimised = intents.with_value(|intents| {
+            edges.with_value(|edges| {
+                layout::optimise(
+                    intents,
+                    edges,
+                    &vectors.get_untracked(),
+                    layout::OPTIMISE_ROUNDS,
+                )
+            })
+        });
+        vectors.set(optimised);
+        if !matches!(layout.get_untracked(), Layout::Additive | Layout::Hybrid) {
+            layout.set(Layout::Additive);
+        }
+    };
 
     let dimensions = RwSignal::new(Dimensions {
         width: 600.0,
@@ -65,36 +236,89 @@ pub fn GraphComp(concepts: Vec<(BitSet, BitSet)>, context: FormalContext<String>
         font_size: 16,
     });
 
-    let mut x_max = 0.0;
-    let mut y_max = 0.0;
-    for node in &graph.nodes {
-        if node.x as f64 > x_max {
-            x_max = node.x as f64;
-        }
-        if node.y as f64 > y_max {
-            y_max = node.y as f64;
-        }
-    }
+    let size_by = RwSignal::new(None::<Measure>);
+    let colour_by = RwSignal::new(None::<Measure>);
 
-    let mut x_not_zero = false;
-    if x_max > 0.0 {
-        x_not_zero = true;
-    }
+    let measures_needed = Memo::new(move |_| size_by.get().is_some() || colour_by.get().is_some());
+    let measures = Memo::new(move |_| {
+        measures_needed
+            .get()
+            .then(|| all_measures(&context, &concepts))
+    });
+
+    // radius and fill of the node of the n-th concept
+    let node_style = move |n: usize| {
+        let radius = dimensions.get().radius;
+        let Some(measures) = measures.get() else {
+            return (radius, "white".to_string());
+        };
+
+        let radius = if let Some(measure) = size_by.get() {
+            radius * (0.6 + 0.8 * normalized(&measures, measure, n))
+        } else {
+            radius
+        };
+        let fill = if let Some(measure) = colour_by.get() {
+            let value = normalized(&measures, measure, n);
+            format!(
+                "rgb({}, {}, {})",
+                (255.0 - 185.0 * value) as u8,
+                (255.0 - 125.0 * value) as u8,
+                (255.0 - 75.0 * value) as u8,
+            )
+        } else {
+            "white".to_string()
+        };
+        (radius, fill)
+    };
+
+    let width_node_ref: NodeRef<leptos::html::Input> = NodeRef::new();
+    let height_node_ref: NodeRef<leptos::html::Input> = NodeRef::new();
 
     let graph_node: NodeRef<leptos::svg::Svg> = NodeRef::new();
-    let offset = RwSignal::new(None);
+    let minimap_node: NodeRef<leptos::svg::Svg> = NodeRef::new();
 
-    Effect::new(move || {
-        let element: web_sys::SvgElement = graph_node.get().unwrap();
-        let rect: web_sys::DomRect = element.get_bounding_client_rect();
+    let view_box = RwSignal::new(ViewBox::fit(&dimensions.get_untracked()));
+    let dragging = RwSignal::new(None::<Drag>);
 
-        let scroll_x = window().scroll_x().unwrap();
-        let scroll_y = window().scroll_y().unwrap();
+    Effect::new(move || view_box.set(ViewBox::fit(&dimensions.get())));
 
-        offset.set(Some((rect.x() + scroll_x, rect.y() + scroll_y)));
-    });
+    let nodes = RwSignal::new(Vec::<Node>::new());
 
-    let nodes = RwSignal::new(Vec::new());
+    let shown_nodes = move || {
+        let visible = visible.get();
+        nodes
+            .get()
+            .into_iter()
+            .filter(|node| visible.contains(node.id))
+            .collect::<Vec<Node>>()
+    };
+    let shown_edges = move || {
+        let visible = visible.get();
+        edges
+            .get_value()
+            .into_iter()
+            .filter(|(start, end)| visible.contains(*start) && visible.contains(*end))
+            .collect::<Vec<(usize, usize)>>()
+    };
+
+    // follows the dragged nodes
+    let metrics = Memo::new(move |_| {
+        let nodes = shown_nodes();
+        let mut position = vec![None; concept_count];
+        for (n, node) in nodes.iter().enumerate() {
+            position[node.id] = Some(n);
+        }
+        let points: Vec<(f64, f64)> = nodes
+            .iter()
+            .map(|node| (node.x_signal.get(), node.y_signal.get()))
+            .collect();
+        let edges: Vec<(usize, usize)> = shown_edges()
+            .into_iter()
+            .filter_map(|(start, end)| Some((position[start]?, position[end]?)))
+            .collect();
+        layout::metrics(&points, &edges, dimensions.get().radius)
+    });
 
     Effect::new(move || {
         let width_input: web_sys::HtmlInputElement = width_node_ref.get().unwrap();
@@ -106,45 +330,107 @@ pub fn GraphComp(concepts: Vec<(BitSet, BitSet)>, context: FormalContext<String>
 
     Effect::new(move || {
         let dimensions = dimensions.get();
+        let positions = positions.get();
+
+        let x_max = positions.iter().map(|p| p.0).fold(0.0, f64::max);
+        let y_max = positions.iter().map(|p| p.1).fold(0.0, f64::max);
 
         let x_coef = (dimensions.width - 2.0 * dimensions.margin) / x_max;
         let y_coef = (dimensions.height - 2.0 * dimensions.margin) / y_max;
 
-        if x_not_zero {
-            nodes.set(
-                graph
-                    .nodes
-                    .iter()
-                    .map(|node| {
-                        Node::new(
-                            node.id,
-                            node.label.clone(),
-                            node.x as f64 * x_coef + dimensions.margin,
-                            node.y as f64 * y_coef + dimensions.margin,
-                        )
-                    })
-                    .collect(),
-            );
+        nodes.set(
+            diagram
+                .nodes
+                .iter()
+                .map(|(n, _, _, label)| {
+                    let (x, y) = positions[*n];
+                    Node::new(
+                        *n,
+                        label.clone(),
+                        if x_max > 0.0 {
+                            x * x_coef + dimensions.margin
+                        } else {
+                            dimensions.width / 2.0
+                        },
+                        y * y_coef + dimensions.margin,
+                    )
+                })
+                .collect(),
+        );
+    });
+
+    let start_pan = move |ev: MouseEvent| {
+        ev.prevent_default();
+        dragging.set(Some(Drag::Pan(ev.client_x() as f64, ev.client_y() as f64)));
+    };
+
+    let drag = move |ev: MouseEvent| {
+        let Some(drag) = dragging.get_untracked() else {
+            return;
+        };
+        let element: web_sys::SvgElement = graph_node.get().unwrap();
+        let (point, viewport) = locate(&element, &ev);
+        let current = view_box.get_untracked();
+
+        match drag {
+            Drag::Node(id) => {
+                let (x, y) = current.to_diagram(viewport, point);
+                let dimensions = dimensions.get_untracked();
+                let radius = node_style(id).0;
+                nodes.with_untracked(|nodes| {
+                    if let Some(node) = nodes.iter().find(|node| node.id == id) {
+                        // border collision
+                        node.x_signal
+                            .set(x.clamp(radius, (dimensions.width - radius).max(radius)));
+                        node.y_signal
+                            .set(y.clamp(radius, (dimensions.height - radius).max(radius)));
+                    }
+                });
+            }
+            Drag::Pan(last_x, last_y) => {
+                let (client_x, client_y) = (ev.client_x() as f64, ev.client_y() as f64);
+                let scale = current.scale(viewport);
+                view_box
+                    .set(current.panned((last_x - client_x) / scale, (last_y - client_y) / scale));
+                dragging.set(Some(Drag::Pan(client_x, client_y)));
+            }
+        }
+    };
+
+    let zoom = move |ev: WheelEvent| {
+        ev.prevent_default();
+        let element: web_sys::SvgElement = graph_node.get().unwrap();
+        let (point, viewport) = locate(&element, &ev);
+        let current = view_box.get_untracked();
+        let factor = if ev.delta_y() > 0.0 {
+            ZOOM_STEP
         } else {
-            nodes.set(
-                graph
-                    .nodes
-                    .iter()
-                    .map(|node| {
-                        Node::new(
-                            node.id,
-                            node.label.clone(),
-                            dimensions.width / 2.0,
-                            node.y as f64 * y_coef + dimensions.margin,
-                        )
-                    })
-                    .collect(),
-            );
+            1.0 / ZOOM_STEP
+        };
+
+        let zoom = dimensions.get_untracked().width / (current.width * factor);
+        if (MIN_ZOOM..=MAX_ZOOM).contains(&zoom) {
+            view_box.set(current.zoomed(factor, current.to_diagram(viewport, point)));
         }
-    });
+    };
+
+    // clicking or dragging on the minimap centres the view there
+    let minimap_move = move |ev: MouseEvent| {
+        if ev.buttons() != 1 {
+            return;
+        }
+        ev.prevent_default();
+        let element: web_sys::SvgElement = minimap_node.get().unwrap();
+        let (point, viewport) = locate(&element, &ev);
+        let whole = ViewBox::fit(&dimensions.get_untracked());
+        view_box.update(|view_box| *view_box = view_box.centred(whole.to_diagram(viewport, point)));
+    };
 
     view! {
         <SvgDownloadComp node_ref=graph_node/>
+        <button style:margin-left="20px" on:click=move |_| {
+            view_box.set(ViewBox::fit(&dimensions.get_untracked()));
+        }>"Fit to Screen"</button>
         <div
             style:margin-top="20px"
             style:display="flex"
@@ -160,7 +446,7 @@ pub fn GraphComp(concepts: Vec<(BitSet, BitSet)>, context: FormalContext<String>
                 type="range"
                 value="600"
                 min={2.0 * dimensions.get().margin}
-                max="1000"
+                max="3000"
                 on:input=move |_| {
                     dimensions.update(|dimen| {
                         let width_input: web_sys::HtmlInputElement = width_node_ref.get().unwrap();
@@ -184,7 +470,7 @@ pub fn GraphComp(concepts: Vec<(BitSet, BitSet)>, context: FormalContext<String>
                 type="range"
                 value="600"
                 min={2.0 * dimensions.get().margin}
-                max="1000"
+                max="3000"
                 on:input=move |_| {
                     dimensions.update(|dimen| {
                         let height_input: web_sys::HtmlInputElement = height_node_ref.get().unwrap();
@@ -193,23 +479,90 @@ pub fn GraphComp(concepts: Vec<(BitSet, BitSet)>, context: FormalContext<String>
                 }
             />
         </div>
+        <div
+            style:margin-top="10px"
+            style:display="flex"
+            style:align-items="center"
+        >
+            <label
+                style:font-family="monospace"
+                style:font-size="18px"
+                style:white-space="pre"
+            >"Layout: "</label>
+            <select
+                prop:value=move || layout.get().name()
+                on:change=move |ev| {
+                    if let Some(chosen) = Layout::from_name(&event_target_value(&ev)) {
+                        layout.set(chosen);
+                    }
+                }
+            >
+                {Layout::ALL.into_iter().map(|choice| {
+                    view! {<option value=choice.name()>{choice.name()}</option>}
+                }).collect_view()}
+            </select>
+            <button style:margin-left="10px" on:click=optimise>"Optimise"</button>
+            <label
+                style:font-family="monospace"
+                style:font-size="18px"
+                style:white-space="pre"
+            >"   Node size by: "</label>
+            <select on:change=move |ev| size_by.set(Measure::from_name(&event_target_value(&ev)))>
+                <option value="">"-"</option>
+                {Measure::ALL.into_iter().map(|measure| {
+                    view! {<option value=measure.name()>{measure.name()}</option>}
+                }).collect_view()}
+            </select>
+            <label
+                style:font-family="monospace"
+                style:font-size="18px"
+                style:white-space="pre"
+            >"   Node colour by: "</label>
+            <select on:change=move |ev| colour_by.set(Measure::from_name(&event_target_value(&ev)))>
+                <option value="">"-"</option>
+                {Measure::ALL.into_iter().map(|measure| {
+                    view! {<option value=measure.name()>{measure.name()}</option>}
+                }).collect_view()}
+            </select>
+        </div>
 
+        <p style:font-family="monospace">{move || {
+            let metrics = metrics.get();
+            format!(
+                "Crossings: {}, edge length variance: {:.1}, overlapping nodes: {}, node-edge conflicts: {}",
+                metrics.crossings,
+                metrics.length_variance,
+                metrics.overlaps,
+                metrics.conflicts,
+            )
+        }}</p>
         <div
-            style:width=move || {format!("{}px", dimensions.get().width)}
-            style:height=move || {format!("{}px", dimensions.get().height)}
+            style:display="flex"
+            style:align-items="flex-start"
+            style:gap="20px"
             style:margin-top="20px"
             style:margin-bottom="20px"
         >
         <svg
             xmlns="http://www.w3.org/2000/svg"
-            style:width=move || {format!("{}px", dimensions.get().width)}
-            style:height=move || {format!("{}px", dimensions.get().height)}
-            // viewBox=move || {format!("0 0 {} {}", dimensions.get().width, dimensions.get().height)}
+            style:width=move || {format!("{}px", viewport_size(&dimensions.get()).0)}
+            style:height=move || {format!("{}px", viewport_size(&dimensions.get()).1)}
+            style:cursor=move || {
+                if let Some(Drag::Pan(..)) = dragging.get() { "grabbing" } else { "grab" }
+            }
+            viewBox=move || view_box.get().attribute()
             node_ref=graph_node
+            on:mousedown=start_pan
+            on:mousemove=drag
+            on:mouseup=move |_| dragging.set(None)
+            on:mouseleave=move |_| dragging.set(None)
+            on:wheel=zoom
         >
+            // the layout is kept with the downloaded diagram
+            <metadata>{move || format!("layout: {}", layout.get().name())}</metadata>
             <rect
-                width="100%"
-                height="100%"
+                width=move || dimensions.get().width
+                height=move || dimensions.get().height
                 x="0"
                 y="0"
                 fill="transparent"
@@ -217,28 +570,31 @@ pub fn GraphComp(concepts: Vec<(BitSet, BitSet)>, context: FormalContext<String>
                 stroke="red"
             />
             {move || {
-                let nodes = nodes.get();
-                if let Some(off) = offset.get() {
+                let nodes = shown_nodes();
+                if !nodes.is_empty() {
                     Either::Left(view! {
                         {
-                            graph.edges.iter().map(|edge| {
-                                let start = nodes.iter().position(|x| x.id == edge.0 as usize).unwrap();
-                                let end = nodes.iter().position(|x| x.id == edge.1 as usize).unwrap();
-                                view! {
+                            shown_edges().into_iter().filter_map(|(start, end)| {
+                                let start = nodes.iter().find(|x| x.id == start)?;
+                                let end = nodes.iter().find(|x| x.id == end)?;
+                                Some(view! {
                                     <EdgeComp
-                                        start=(nodes[start].x_signal, nodes[start].y_signal)
-                                        end=(nodes[end].x_signal, nodes[end].y_signal)
+                                        start=(start.x_signal, start.y_signal)
+                                        end=(end.x_signal, end.y_signal)
                                     />
-                                }
+                                })
                             }).collect_view()
                         }
                         {
                             nodes.iter().map(|node| {
+                                let (radius, fill) = node_style(node.id);
                                 view! {
                                     <NodeComp
                                         node=node.clone()
-                                        offset=off
                                         dimensions=dimensions.get()
+                                        radius=radius
+                                        fill=fill
+                                        dragging=dragging
                                     />
                                 }
                             }).collect_view()
@@ -254,7 +610,15 @@ pub fn GraphComp(concepts: Vec<(BitSet, BitSet)>, context: FormalContext<String>
                 } else {
                     Either::Right(
                         view! {
-                            <rect width="100%" height="100%" x="0" y="0" fill="white" stroke-width="3" stroke="red"/>
+                            <rect
+                                width=dimensions.get().width
+                                height=dimensions.get().height
+                                x="0"
+                                y="0"
+                                fill="white"
+                                stroke-width="3"
+                                stroke="red"
+                            />
                             <text
                                 font-size=dimensions.get().font_size as f64 * 1.6
                                 dy=".35em"
@@ -269,6 +633,63 @@ pub fn GraphComp(concepts: Vec<(BitSet, BitSet)>, context: FormalContext<String>
                 }
             }}
         </svg>
+
+        // minimap
+        <svg
+            xmlns="http://www.w3.org/2000/svg"
+            style:width=format!("{}px", MINIMAP_WIDTH)
+            style:height=move || {
+                let dimensions = dimensions.get();
+                format!("{}px", MINIMAP_WIDTH * dimensions.height / dimensions.width)
+            }
+            style:border="1px solid black"
+            style:cursor="pointer"
+            viewBox=move || ViewBox::fit(&dimensions.get()).attribute()
+            node_ref=minimap_node
+            on:mousedown=minimap_move
+            on:mousemove=minimap_move
+        >
+            {move || {
+                let nodes = shown_nodes();
+                let radius = dimensions.get().radius;
+                view! {
+                    {
+                        shown_edges().into_iter().filter_map(|(start, end)| {
+                            let start = nodes.iter().find(|x| x.id == start)?;
+                            let end = nodes.iter().find(|x| x.id == end)?;
+                            Some(view! {
+                                <EdgeComp
+                                    start=(start.x_signal, start.y_signal)
+                                    end=(end.x_signal, end.y_signal)
+                                />
+                            })
+                        }).collect_view()
+                    }
+                    {
+                        nodes.iter().map(|node| {
+                            view! {
+                                <circle
+                                    fill="black"
+                                    r=radius
+                                    cx=node.x_signal
+                                    cy=node.y_signal
+                                />
+                            }
+                        }).collect_view()
+                    }
+                }
+            }}
+            <rect
+                x=move || view_box.get().x
+                y=move || view_box.get().y
+                width=move || view_box.get().width
+                height=move || view_box.get().height
+                fill="blue"
+                fill-opacity="0.1"
+                stroke="blue"
+                stroke-width=move || dimensions.get().width / MINIMAP_WIDTH * 2.0
+            />
+        </svg>
         </div>
     }
 }
@@ -0,0 +1,111 @@
+use leptos::{either::Either, prelude::*};
+
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::fca::{
+    derivation::format_set,
+    implications::{closure, counterexamples, entails, parse_implication, parse_set},
+};
+
+#[component]
+pub fn ImplicationComp(
+    context: RwSignal<FormalContext<String>>,
+    basis: RwSignal<Option<Vec<(BitSet, BitSet)>>>,
+    // a basis that is still arriving from the worker is incomplete
+    basis_running: RwSignal<bool>,
+    // the context the basis was computed for
+    basis_context: RwSignal<FormalContext<String>>,
+) -> impl IntoView {
+    let premise = RwSignal::new(String::new());
+    let implication = RwSignal::new(String::new());
+
+    // the table drops the basis after an edit, it is not used until then
+    let current_basis = move || {
+        if basis_running.get()
+            || basis_context.with(|basis_context| {
+                context.with(|context| basis_context.attributes != context.attributes)
+            })
+        {
+            return None;
+        }
+        basis.get()
+    };
+
+    view! {
+        <p>"Closure under the canonical base:"</p>
+        <input type="text" placeholder="a, b, ..." bind:value=premise/>
+        <div>
+            {move || {
+                context.get().attributes.into_iter().enumerate().map(|(m, name)| {
+                    view! {
+                        <button
+                            style:margin="2px"
+                            on:click=move |_| {
+                                premise.update(|text| {
+                                    if !text.trim().is_empty() {
+                                        text.push_str(", ");
+                                    }
+                                    text.push_str(&context.get().attributes[m]);
+                                });
+                            }
+                        >{name}</button>
+                    }
+                }).collect_view()
+            }}
+        </div>
+        <p style:white-space="pre-wrap">{move || {
+            let attributes = context.get().attributes;
+            match parse_set(&premise.get(), &attributes) {
+                Ok(set) => {
+                    let Some(basis) = current_basis() else {
+                        return "Compute the canonical base first.".to_string();
+                    };
+                    let hull = closure(&basis, &set);
+                    let added: BitSet = hull.difference(&set).collect();
+                    format!(
+                        "Closure: {}\nAdded:   {}",
+                        format_set(&hull, &attributes),
+                        format_set(&added, &attributes),
+                    )
+                }
+                Err(error) => error,
+            }
+        }}</p>
+
+        <p>"Check an implication:"</p>
+        <input type="text" placeholder="a, b -> c" bind:value=implication/>
+        {move || {
+            let context = context.get();
+            if implication.get().trim().is_empty() {
+                return Either::Right(view! {<p>{"...".to_string()}</p>});
+            }
+            match parse_implication(&implication.get(), &context.attributes) {
+                Ok((premise, conclusion)) => {
+                    let entailed = match current_basis() {
+                        Some(basis) if entails(&basis, &premise, &conclusion) => "yes",
+                        Some(_) => "no",
+                        None => "compute the canonical base first",
+                    };
+                    let counterexamples = counterexamples(&context, &premise, &conclusion);
+
+                    Either::Left(view! {
+                        <p style:white-space="pre-wrap">{format!(
+                            "Entailed by the canonical base: {}\nHolds in the context: {}",
+                            entailed,
+                            if counterexamples.is_empty() { "yes" } else { "no" },
+                        )}</p>
+                        <p>{
+                            if counterexamples.is_empty() {
+                                "".to_string()
+                            } else {
+                                format!("Counterexamples: {}", format_set(&counterexamples, &context.objects))
+                            }
+                        }</p>
+                    })
+                }
+                Err(error) => Either::Right(view! {<p>{error}</p>}),
+            }
+        }}
+    }
+}
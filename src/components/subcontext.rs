@@ -0,0 +1,117 @@
+use leptos::prelude::*;
+
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::fca::combine;
+
+#[component]
+pub fn SubcontextComp(
+    context: RwSignal<FormalContext<String>>,
+    working_context: RwSignal<Option<FormalContext<String>>>,
+) -> impl IntoView {
+    let show_dialog = RwSignal::new("none");
+
+    let objects = RwSignal::new(BitSet::new());
+    let attributes = RwSignal::new(BitSet::new());
+    let error = RwSignal::new("");
+
+    view! {
+        <button
+            style:margin-left="20px"
+            on:click=move |_| {
+                objects.set((0..context.get().objects.len()).collect());
+                attributes.set((0..context.get().attributes.len()).collect());
+                show_dialog.set("block");
+        }>"Extract Subcontext"</button>
+
+        <div
+            style:opacity="0.6"
+            style:background-color="#ccc"
+            style:position="fixed"
+            style:width="100%"
+            style:height="100%"
+            style:top="0px"
+            style:left="0px"
+            style:z-index="10"
+            style:display=show_dialog
+        />
+
+        <div
+            style:position="absolute"
+            style:top="50%"
+            style:left="50%"
+            style:transform="translate(-50%, -50%)"
+            style:background="white"
+            style:border="thin solid black"
+            style:z-index="11"
+            style:display=show_dialog
+        >
+            <div
+                style:margin="10px"
+            >
+                <p>"Select the rows and columns of the subcontext:"</p>
+                <div style:display="flex">
+                    <div style:min-width="200px" style:max-height="300px" style:overflow-y="scroll">
+                        {move || {
+                            context.get().objects.into_iter().enumerate().map(|(g, name)| {
+                                view! {
+                                    <label style:display="block">
+                                        <input
+                                            type="checkbox"
+                                            prop:checked=move || objects.get().contains(g)
+                                            on:change=move |_| objects.update(|set| {
+                                                if !set.remove(g) {
+                                                    set.insert(g);
+                                                }
+                                            })
+                                        />
+                                        {name}
+                                    </label>
+                                }
+                            }).collect_view()
+                        }}
+                    </div>
+                    <div style:min-width="200px" style:max-height="300px" style:overflow-y="scroll">
+                        {move || {
+                            context.get().attributes.into_iter().enumerate().map(|(m, name)| {
+                                view! {
+                                    <label style:display="block">
+                                        <input
+                                            type="checkbox"
+                                            prop:checked=move || attributes.get().contains(m)
+                                            on:change=move |_| attributes.update(|set| {
+                                                if !set.remove(m) {
+                                                    set.insert(m);
+                                                }
+                                            })
+                                        />
+                                        {name}
+                                    </label>
+                                }
+                            }).collect_view()
+                        }}
+                    </div>
+                </div>
+
+                <p style:color="red">{move || error.get()}</p>
+
+                <button on:click=move |_| {
+                    if objects.get().is_empty() || attributes.get().is_empty() {
+                        error.set("Select at least one object and one attribute.");
+                        return;
+                    }
+
+                    let result = combine::subcontext(&context.get(), &objects.get(), &attributes.get());
+                    show_dialog.set("none");
+                    error.set("");
+                    working_context.set(Some(result));
+                }>"Extract"</button>
+                <button on:click=move |_| {
+                    show_dialog.set("none");
+                    error.set("");
+                }>"Cancel"</button>
+            </div>
+        </div>
+    }
+}
@@ -1,86 +1,40 @@
-use crate::components::graph::{Dimensions, Node};
-use leptos::{ev, prelude::*};
-use leptos_use::{
-    UseDraggableOptions, UseDraggableReturn, core::Position, use_draggable_with_options,
-};
+use crate::components::graph::{Dimensions, Drag, Node};
+use leptos::prelude::*;
 use web_sys::MouseEvent;
 
 #[component]
-pub fn NodeComp(node: Node, offset: (f64, f64), dimensions: Dimensions) -> impl IntoView {
-    let node_ref: NodeRef<leptos::svg::G> = NodeRef::new();
-
-    let UseDraggableReturn { x, y, .. } = use_draggable_with_options(
-        node_ref,
-        UseDraggableOptions::default()
-            .initial_value(Position {
-                x: node.x - dimensions.radius + offset.0,
-                y: node.y - dimensions.radius + offset.1,
-            })
-            .prevent_default(true),
-    );
-
-    let scroll_offset = RwSignal::new((0.0, 0.0));
-
-    let scroll = move |_: MouseEvent| {
-        let scroll_x = window().scroll_x().unwrap();
-        let scroll_y = window().scroll_y().unwrap();
-
-        scroll_offset.set((scroll_x, scroll_y));
+pub fn NodeComp(
+    node: Node,
+    dimensions: Dimensions,
+    radius: f64,
+    fill: String,
+    dragging: RwSignal<Option<Drag>>,
+) -> impl IntoView {
+    // the drag itself is followed by the diagram, which knows the zoom
+    let grab = move |ev: MouseEvent| {
+        ev.prevent_default();
+        ev.stop_propagation();
+        dragging.set(Some(Drag::Node(node.id)));
     };
 
-    let handle = window_event_listener(ev::scroll, move |_| {
-        let scroll_x = window().scroll_x().unwrap();
-        let scroll_y = window().scroll_y().unwrap();
-
-        scroll_offset.set((scroll_x, scroll_y));
-    });
-    on_cleanup(move || handle.remove());
-
-    // border collision x
-    let x_pos = move || {
-        let pos = x.get() + dimensions.radius - offset.0 + scroll_offset.get_untracked().0;
-        if pos > dimensions.width - dimensions.radius {
-            node.x_signal.set(dimensions.width - dimensions.radius);
-            return dimensions.width - dimensions.radius;
-        } else if pos < dimensions.radius {
-            node.x_signal.set(dimensions.radius);
-            return dimensions.radius;
-        } else {
-            node.x_signal.set(pos);
-            return pos;
-        }
-    };
-
-    // border collision y
-    let y_pos = move || {
-        let pos = y.get() + dimensions.radius - offset.1 + scroll_offset.get_untracked().1;
-        if pos > dimensions.height - dimensions.radius {
-            node.y_signal.set(dimensions.height - dimensions.radius);
-            return dimensions.height - dimensions.radius;
-        } else if pos < dimensions.radius {
-            node.y_signal.set(dimensions.radius);
-            return dimensions.radius;
-        } else {
-            node.y_signal.set(pos);
-            return pos;
-        }
-    };
+    let x_pos = move || node.x_signal.get();
+    let y_pos = move || node.y_signal.get();
 
     view! {
         <g
             class="prevent-select"
         >
             <g
-                node_ref=node_ref
+                style:cursor="grab"
+                on:mousedown=grab
             >
                 <circle
-                    fill="white"
+                    fill=fill
                     stroke="black"
                     stroke-width="2"
-                    r=dimensions.radius
+                    r=radius
                     cx=x_pos
                     cy=y_pos
-                    on:mouseover=scroll
                 />
             </g>
 
@@ -93,7 +47,7 @@ pub fn NodeComp(node: Node, offset: (f64, f64), dimensions: Dimensions) -> impl
                 stroke-width="0.3em"
                 font-family="monospace"
                 x=x_pos
-                y=move || {y_pos() + dimensions.radius * 2.8}
+                y=move || {y_pos() + radius * 2.8}
             >{
                 if let Some(obj) = node.label.0.clone() {
                     let len = obj.len();
@@ -111,7 +65,7 @@ pub fn NodeComp(node: Node, offset: (f64, f64), dimensions: Dimensions) -> impl
                 fill="black"
                 font-family="monospace"
                 x=x_pos
-                y=move || {y_pos() + dimensions.radius * 2.8}
+                y=move || {y_pos() + radius * 2.8}
             >{
                 if let Some(obj) = node.label.0 {
                     obj
@@ -130,7 +84,7 @@ pub fn NodeComp(node: Node, offset: (f64, f64), dimensions: Dimensions) -> impl
                 font-style="italic"
                 font-family="monospace"
                 x=x_pos
-                y=move || {y_pos() - dimensions.radius * 2.8}
+                y=move || {y_pos() - radius * 2.8}
             >{
                 if let Some(attr) = node.label.1.clone() {
                     let len = attr.len();
@@ -149,7 +103,7 @@ pub fn NodeComp(node: Node, offset: (f64, f64), dimensions: Dimensions) -> impl
                 font-style="italic"
                 font-family="monospace"
                 x=x_pos
-                y=move || {y_pos() - dimensions.radius * 2.8}
+                y=move || {y_pos() - radius * 2.8}
             >{
                 if let Some(attr) = node.label.1 {
                     attr
@@ -1,12 +1,30 @@
-use leptos::{either::Either, prelude::*};
+use leptos::{either::Either, logging, prelude::*};
 
 use bit_set::BitSet;
 use odis::FormalContext;
-use std::collections::HashMap;
-use web_sys::MouseEvent;
-
-use crate::components::{
-    checkbox::CheckboxComp, download::DownloadComp, exploration::ExplorationComp, graph::GraphComp,
+use std::collections::{HashMap, HashSet};
+use web_sys::{MessageEvent, Worker, wasm_bindgen::closure::Closure};
+
+use crate::{
+    components::{
+        algorithms::AlgorithmComparisonComp,
+        association::AssociationComp,
+        bases::BasesComp,
+        checkbox::CheckboxComp,
+        closed_sets::ClosedSetsComp,
+        combine::CombineComp,
+        concept_list::ConceptListComp,
+        derivation::DerivationComp,
+        download::{DownloadComp, to_burmeister},
+        exploration::ExplorationComp,
+        generator::GeneratorComp,
+        graph::GraphComp,
+        implication::ImplicationComp,
+        subcontext::SubcontextComp,
+    },
+    fca::{concepts::Algorithm, layout::Layout},
+    js_fn,
+    worker_protocol::{Diagram, Job, Message, decode_message, encode_job},
 };
 
 #[derive(Debug, Clone)]
@@ -17,10 +35,165 @@ pub struct Table {
     pub column_data: Vec<(usize, usize)>,
     // ((pos_row, pos_column), checked)
     pub boxes: HashMap<(usize, usize), RwSignal<bool>>,
+    // (pos_row, pos_column) of incidences that are not known yet
+    pub unknown: HashSet<(usize, usize)>,
+}
+
+// Appends an object to the context and keeps the table in sync.
+pub fn push_object(
+    context: RwSignal<FormalContext<String>>,
+    table: RwSignal<Table>,
+    row_key: RwSignal<usize>,
+    object_names: RwSignal<Vec<NodeRef<leptos::html::Input>>>,
+    name: String,
+    intent: &BitSet,
+) {
+    let num_obj = table.get().row_data.len();
+
+    table.update(|table| table.row_data.push((row_key.get(), num_obj)));
+    row_key.update(|key| *key += 1);
+
+    for n in 0..table.read_only().get().column_data.len() {
+        table.update(|table| {
+            table
+                .boxes
+                .insert((num_obj, n), RwSignal::new(intent.contains(n)));
+        });
+    }
+
+    context.update(|context| {
+        context.add_object(name, intent);
+    });
+
+    object_names.update(|list| list.push(NodeRef::new()));
+}
+
+// Removes the last object, the inverse of `push_object`.
+pub fn pop_object(
+    context: RwSignal<FormalContext<String>>,
+    table: RwSignal<Table>,
+    object_names: RwSignal<Vec<NodeRef<leptos::html::Input>>>,
+) {
+    let index = table.get().row_data.len() - 1;
+
+    for attr in 0..table.read_only().get().column_data.len() {
+        table.update(|table| {
+            table.boxes.remove(&(index, attr));
+        });
+    }
+    table.update(|table| {
+        table.row_data.retain(|row| row.1 != index);
+        table.unknown.retain(|cell| cell.0 != index);
+    });
+
+    context.update(|context| {
+        context.remove_object(index);
+    });
+
+    object_names.update(|list| {
+        list.pop();
+    });
+}
+
+// Appends an attribute to the context and keeps the table in sync.
+pub fn push_attribute(
+    context: RwSignal<FormalContext<String>>,
+    table: RwSignal<Table>,
+    column_key: RwSignal<usize>,
+    attribute_names: RwSignal<Vec<NodeRef<leptos::html::Input>>>,
+    name: String,
+    extent: &BitSet,
+) {
+    let num_attr = table.get().column_data.len();
+
+    table.update(|table| table.column_data.push((column_key.get(), num_attr)));
+    column_key.update(|key| *key += 1);
+
+    for n in 0..table.read_only().get().row_data.len() {
+        table.update(|table| {
+            table
+                .boxes
+                .insert((n, num_attr), RwSignal::new(extent.contains(n)));
+        });
+    }
+
+    context.update(|context| {
+        context.add_attribute(name, extent);
+    });
+
+    attribute_names.update(|list| list.push(NodeRef::new()));
+}
+
+// Removes the last attribute, the inverse of `push_attribute`.
+pub fn pop_attribute(
+    context: RwSignal<FormalContext<String>>,
+    table: RwSignal<Table>,
+    attribute_names: RwSignal<Vec<NodeRef<leptos::html::Input>>>,
+) {
+    let index = table.get().column_data.len() - 1;
+
+    for obj in 0..table.read_only().get().row_data.len() {
+        table.update(|table| {
+            table.boxes.remove(&(obj, index));
+        });
+    }
+    table.update(|table| {
+        table.column_data.retain(|column| column.1 != index);
+        table.unknown.retain(|cell| cell.1 != index);
+    });
+
+    context.update(|context| {
+        context.remove_attribute(index);
+    });
+
+    attribute_names.update(|list| {
+        list.pop();
+    });
+}
+
+// Swaps in another context without remounting the table, so components keep their state.
+pub fn replace_context(
+    context: RwSignal<FormalContext<String>>,
+    table: RwSignal<Table>,
+    row_key: RwSignal<usize>,
+    column_key: RwSignal<usize>,
+    object_names: RwSignal<Vec<NodeRef<leptos::html::Input>>>,
+    attribute_names: RwSignal<Vec<NodeRef<leptos::html::Input>>>,
+    new: &FormalContext<String>,
+) {
+    while !context.get().objects.is_empty() {
+        pop_object(context, table, object_names);
+    }
+    while !context.get().attributes.is_empty() {
+        pop_attribute(context, table, attribute_names);
+    }
+
+    for name in &new.attributes {
+        push_attribute(
+            context,
+            table,
+            column_key,
+            attribute_names,
+            name.clone(),
+            &BitSet::new(),
+        );
+    }
+    for (g, name) in new.objects.iter().enumerate() {
+        push_object(
+            context,
+            table,
+            row_key,
+            object_names,
+            name.clone(),
+            &new.atomic_object_derivations[g],
+        );
+    }
 }
 
 #[component]
 pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoView {
+    let working_context = context;
+
     let mut temp_context = RwSignal::new(FormalContext::new());
     if let Some(n) = context.get() {
         temp_context = RwSignal::new(n);
@@ -42,18 +215,28 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
         row_data: Vec::new(),
         column_data: Vec::new(),
         boxes: HashMap::new(),
+        unknown: HashSet::new(),
     });
 
     let object_names: RwSignal<Vec<NodeRef<leptos::html::Input>>> = RwSignal::new(Vec::new());
     let attribute_names: RwSignal<Vec<NodeRef<leptos::html::Input>>> = RwSignal::new(Vec::new());
 
     let concepts = RwSignal::new(None);
+    // the context the concepts were computed for, later edits do not change their indices
+    let concepts_context = RwSignal::new(FormalContext::new());
+    let algorithm = RwSignal::new(Algorithm::FCbO);
     let concept_lattice = RwSignal::new(false);
     let basis = RwSignal::new(None);
+    let basis_context = RwSignal::new(FormalContext::new());
+    let min_support = RwSignal::new(0.0);
+    let layout = RwSignal::new(Layout::Layered);
+    let vectors = RwSignal::new(Vec::new());
 
     let focus_pos: RwSignal<(usize, usize)> = RwSignal::new((0, 0));
     let delete_hover_obj = RwSignal::new(false);
     let delete_hover_attr = RwSignal::new(false);
+    // (extent, intent) of the cells marked by the derivation calculator
+    let highlight = RwSignal::new((BitSet::new(), BitSet::new()));
 
     for g in 0..context.get_untracked().objects.len() {
         table.update(|table| {
@@ -88,22 +271,14 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
     }
 
     let add_object = move |_| {
-        let num_obj = table.get().row_data.last().unwrap().1;
-
-        table.update(|table| table.row_data.push((row_key.get(), num_obj + 1)));
-        row_key.update(|key| *key += 1);
-
-        for n in 0..table.read_only().get().column_data.len() {
-            table.update(|table| {
-                table.boxes.insert((num_obj + 1, n), RwSignal::new(false));
-            });
-        }
-
-        context.update(|context| {
-            context.add_object("Object".to_string(), &BitSet::new());
-        });
-
-        object_names.update(|list| list.push(NodeRef::new()));
+        push_object(
+            context,
+            table,
+            row_key,
+            object_names,
+            "Object".to_string(),
+            &BitSet::new(),
+        );
     };
 
     let remove_object = move |_| {
@@ -111,7 +286,12 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
             return;
         };
 
-        let index = focus_pos.read_only().get().0;
+        // the focused row may be gone after an exploration changed the context
+        let index = focus_pos
+            .read_only()
+            .get()
+            .0
+            .min(table.read_only().get().row_data.len() - 1);
 
         for attr in 0..table.read_only().get().column_data.len() {
             table.update(|table| {
@@ -126,6 +306,12 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
         }
 
         table.update(|table| {
+            table.unknown = table
+                .unknown
+                .iter()
+                .filter(|cell| cell.0 != index)
+                .map(|&(obj, attr)| (if obj > index { obj - 1 } else { obj }, attr))
+                .collect();
             table.row_data.remove(index);
             table.row_data = table
                 .row_data
@@ -156,22 +342,14 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
     };
 
     let add_attribute = move |_| {
-        let num_attr = table.get().column_data.last().unwrap().1;
-
-        table.update(|table| table.column_data.push((column_key.get(), num_attr + 1)));
-        column_key.update(|key| *key += 1);
-
-        for n in 0..table.read_only().get().row_data.len() {
-            table.update(|table| {
-                table.boxes.insert((n, num_attr + 1), RwSignal::new(false));
-            });
-        }
-
-        context.update(|context| {
-            context.add_attribute("Attribute".to_string(), &BitSet::new());
-        });
-
-        attribute_names.update(|list| list.push(NodeRef::new()));
+        push_attribute(
+            context,
+            table,
+            column_key,
+            attribute_names,
+            "Attribute".to_string(),
+            &BitSet::new(),
+        );
     };
 
     let remove_attribute = move |_| {
@@ -179,7 +357,11 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
             return;
         };
 
-        let index = focus_pos.read_only().get().1;
+        let index = focus_pos
+            .read_only()
+            .get()
+            .1
+            .min(table.read_only().get().column_data.len() - 1);
 
         for obj in 0..table.read_only().get().row_data.len() {
             table.update(|table| {
@@ -194,6 +376,12 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
         }
 
         table.update(|table| {
+            table.unknown = table
+                .unknown
+                .iter()
+                .filter(|cell| cell.1 != index)
+                .map(|&(obj, attr)| (obj, if attr > index { attr - 1 } else { attr }))
+                .collect();
             table.column_data.remove(index);
             table.column_data = table
                 .column_data
@@ -223,20 +411,128 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
         }
     };
 
+    // one worker per kind of job, so computing the basis does not interrupt the concepts;
+    // a new worker is started for every job
+    let concept_worker = StoredValue::new_local(None::<(Worker, Closure<dyn FnMut(MessageEvent)>)>);
+    let basis_worker = StoredValue::new_local(None::<(Worker, Closure<dyn FnMut(MessageEvent)>)>);
+    let concepts_running = RwSignal::new(false);
+    let basis_running = RwSignal::new(false);
+    let concepts_found = RwSignal::new(0);
+    let implications_found = RwSignal::new(0);
+    // the layout of the lattice, computed by the worker after the concepts
+    let diagram = RwSignal::new(None::<Diagram>);
+
+    let stop = move |basis_job: bool| {
+        let worker = if basis_job {
+            basis_worker
+        } else {
+            concept_worker
+        };
+        worker.update_value(|worker| {
+            if let Some((worker, _)) = worker.take() {
+                worker.terminate();
+            }
+        });
+    };
+
+    // an interrupted result is incomplete and is dropped
+    let cancel = move |basis_job: bool| {
+        stop(basis_job);
+        if basis_job {
+            basis_running.set(false);
+            basis.set(None);
+        } else {
+            concepts_running.set(false);
+            concepts.set(None);
+            diagram.set(None);
+        }
+    };
+
+    let run_job = move |job: Job| {
+        let basis_job = job == Job::Basis;
+        stop(basis_job);
+
+        if basis_job {
+            implications_found.set(0);
+            basis.set(Some(Vec::new()));
+            basis_context.set(context.get_untracked());
+            basis_running.set(true);
+        } else {
+            concepts_found.set(0);
+            concepts.set(Some(Vec::new()));
+            concepts_context.set(context.get_untracked());
+            diagram.set(None);
+            concepts_running.set(true);
+        }
+        let running = if basis_job {
+            basis_running
+        } else {
+            concepts_running
+        };
+
+        let message = encode_job(&job, &to_burmeister(&context.get_untracked()));
+        let worker = js_fn::start_worker(message, move |text| match decode_message(&text) {
+            Some(Message::Concepts(batch)) => {
+                concepts_found.update(|n| *n += batch.len());
+                concepts.update(|concepts| concepts.get_or_insert_with(Vec::new).extend(batch));
+            }
+            Some(Message::Diagram(layout)) => diagram.set(Some(layout)),
+            Some(Message::Implications(batch)) => {
+                implications_found.update(|n| *n += batch.len());
+                basis.update(|basis| basis.get_or_insert_with(Vec::new).extend(batch));
+            }
+            Some(Message::Done) => {
+                if !basis_job {
+                    concepts.update(|concepts| {
+                        if let Some(concepts) = concepts {
+                            concepts_context.get_untracked().sort_lectic_order(concepts);
+                        }
+                    });
+                    if diagram.with_untracked(Option::is_some) {
+                        concept_lattice.set(true);
+                    }
+                }
+                running.set(false);
+            }
+            Some(Message::Error(error)) => {
+                logging::log!("Worker error: {}", error);
+                running.set(false);
+                if basis_job {
+                    basis.set(None);
+                } else {
+                    concepts.set(None);
+                }
+            }
+            _ => {}
+        });
+        if basis_job {
+            basis_worker.set_value(Some(worker));
+        } else {
+            concept_worker.set_value(Some(worker));
+        }
+    };
+
+    // results of an earlier context are dropped with every edit
+    Effect::new(move |_| {
+        context.track();
+        cancel(false);
+        cancel(true);
+        concept_lattice.set(false);
+    });
+
     let calc_concepts = move |_| {
-        let mut result: Vec<(BitSet, BitSet)> =
-            context.read_only().get().fcbo_index_concepts().collect();
-        context.get().sort_lectic_order(&mut result);
-        concepts.set(Some(result));
+        run_job(Job::Concepts(algorithm.get().name().to_string()));
     };
 
     let calc_basis = move |_| {
-        let result = context.read_only().get().canonical_basis();
-        basis.set(Some(result));
+        run_job(Job::Basis);
     };
 
     view! {
         <DownloadComp context=context/>
+        <CombineComp context=context working_context=working_context/>
+        <SubcontextComp context=context working_context=working_context/>
+        <GeneratorComp working_context=working_context/>
         <br/><br/><br/>
 
         <button on:click=add_object>"Add Object"</button>
@@ -322,6 +618,8 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
                                                         "lightblue"
                                                     } else if (row.1, column.1) == focus_pos.get() {
                                                         "lightblue"
+                                                    } else if highlight.get().0.contains(row.1) && highlight.get().1.contains(column.1) {
+                                                        "lightgreen"
                                                     } else {
                                                         "#D3D3D3"
                                                     }
@@ -342,79 +640,62 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
             </tbody>
         </table>
         <br/>
+        <DerivationComp context=context highlight=highlight/>
+        <br/><br/>
+        <div style:display="flex" style:align-items="center">
+            <label style:white-space="pre">"Minimum support: "</label>
+            <input
+                type="range"
+                min="0"
+                max="100"
+                value="0"
+                on:change=move |ev| {
+                    min_support.set(event_target_value(&ev).parse::<f64>().unwrap_or(0.0) / 100.0);
+                }
+            />
+            <label style:white-space="pre">{move || format!(" {:.0}%", min_support.get() * 100.0)}</label>
+        </div>
         <div style:display="flex">
             <div style:min-width="200px" style:max-width="40%">
                 <button on:click=calc_concepts>"Compute Concepts"</button>
-                {move || {
-                    if let Some(n) = concepts.get() {
-                        let concepts_clone: Vec<(usize, (BitSet, BitSet))> = concepts.get().unwrap().into_iter().enumerate().collect();
-                        Either::Left(view! {
-                            <p>{format!("The number of concepts is: {}", n.len())}</p>
-                            <ul style:max-height="300px" style:overflow-y="scroll">
-                                <For
-                                    each=move || concepts_clone.clone()
-                                    key=|key| key.0
-                                    children=move |concept| {
-                                        view! {
-                                            <li style:white-space="pre">
-                                                {
-                                                    let mut obj_string = String::new();
-                                                    obj_string.push('{');
-
-                                                    for n in &concept.1.0 {
-                                                        obj_string.push_str(
-                                                            &(" ".to_string() + &context.get().objects[n] + " ,")
-                                                        );
-                                                    }
-
-                                                    if concept.1.0.len() > 0 {
-                                                        obj_string.pop();
-                                                    } else {
-                                                        obj_string.push(' ');
-                                                    }
-                                                    obj_string.push('}');
-
-                                                    let mut white_spaces = String::from("   ");
-                                                    if concept.0 >= 9 {
-                                                        white_spaces.truncate(1);
-                                                    }
-
-                                                    format!("{}:{}{},", concept.0 + 1, white_spaces, obj_string)
-                                                }
-                                                <br/>
-                                                {
-                                                    let mut attr_string = String::new();
-                                                    attr_string.push('{');
-
-                                                    for n in &concept.1.1 {
-                                                        attr_string.push_str(
-                                                            &(" ".to_string() + &context.get().attributes[n] + " ,")
-                                                        );
-                                                    }
-                                                    if concept.1.1.len() > 0 {
-                                                        attr_string.pop();
-                                                    } else {
-                                                        attr_string.push(' ');
-                                                    }
-                                                    attr_string.push('}');
-
-                                                    let white_spaces = String::from("      ");
-
-                                                    format!("{}{}", white_spaces, attr_string)
-                                                }
-                                            </li>
-                                        }
-                                    }
-                                />
-                            </ul>
-                        })
-                    } else {
-                        Either::Right(view! {<p>"..."</p>})
+                <select on:change=move |ev| {
+                    if let Some(n) = Algorithm::from_name(&event_target_value(&ev)) {
+                        algorithm.set(n);
                     }
+                }>
+                    {Algorithm::ALL.into_iter().map(|n| {
+                        view! {
+                            <option value=n.name() selected=n == Algorithm::FCbO>{n.name()}</option>
+                        }
+                    }).collect_view()}
+                </select>
+                {move || {
+                    concepts_running.get().then(|| view! {
+                        <p>
+                            {move || format!("Computing concepts… {} found ", concepts_found.get())}
+                            <button on:click=move |_| cancel(false)>"Cancel"</button>
+                        </p>
+                    })
                 }}
+                <br/>
+                <AlgorithmComparisonComp context=context/>
+                <ConceptListComp
+                    context=concepts_context
+                    concepts=concepts
+                    min_support=min_support
+                    running=concepts_running
+                />
             </div>
             <div style:min-width="200px" style:max-width="40%">
                 <button on:click=calc_basis>"Compute Canonical Base"</button>
+                {move || {
+                    basis_running.get().then(|| view! {
+                        <p>
+                            {move || format!("Computing implications… {} found ", implications_found.get())}
+                            <button on:click=move |_| cancel(true)>"Cancel"</button>
+                        </p>
+                    })
+                }}
                 {move || {
                     if let Some(n) = basis.get() {
                         let basis_clone: Vec<(usize, (BitSet, BitSet))> = basis.get().unwrap().into_iter().enumerate().collect();
@@ -433,7 +714,7 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
 
                                                     for n in &basis.1.0 {
                                                         premise.push_str(
-                                                            &(" ".to_string() + &context.get().attributes[n] + " ,")
+                                                            &(" ".to_string() + &basis_context.get().attributes[n] + " ,")
                                                         );
                                                     }
 
@@ -458,7 +739,7 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
 
                                                     for n in &basis.1.1 {
                                                         conclusion.push_str(
-                                                            &(" ".to_string() + &context.get().attributes[n] + " ,")
+                                                            &(" ".to_string() + &basis_context.get().attributes[n] + " ,")
                                                         );
                                                     }
                                                     if basis.1.1.len() > 0 {
@@ -482,26 +763,54 @@ pub fn TableComp(context: RwSignal<Option<FormalContext<String>>>) -> impl IntoV
                         Either::Right(view! {<p>"..."</p>})
                     }
                 }}
+                <ImplicationComp
+                    context=context
+                    basis=basis
+                    basis_running=basis_running
+                    basis_context=basis_context
+                />
+                <br/>
+                <BasesComp context=context/>
+                <br/>
+                <ClosedSetsComp context=context/>
+            </div>
+            <div style:min-width="200px" style:max-width="40%">
+                <AssociationComp
+                    context=concepts_context
+                    concepts=concepts
+                    running=concepts_running
+                />
             </div>
             <div>
                 <ExplorationComp
                     context=context
                     table=table
                     row_key=row_key
+                    column_key=column_key
                     object_names=object_names
+                    attribute_names=attribute_names
+                    canonical_base=basis
+                    basis_running=basis_running
+                    basis_context=basis_context
                 />
             </div>
 
         </div>
 
         <button on:click=move |_| {
-            calc_concepts(MouseEvent::new("click").unwrap());
-            concept_lattice.set(true);
+            run_job(Job::Lattice(algorithm.get().name().to_string()));
         }>"Draw Concept Lattice"</button>
         {move || {
             if concept_lattice.get() {
                 Either::Left(view! {
-                    <GraphComp concepts=concepts.get_untracked().unwrap() context=context.get_untracked()/>
+                    <GraphComp
+                        concepts=concepts.get_untracked().unwrap()
+                        context=concepts_context.get_untracked()
+                        diagram=diagram.get_untracked().unwrap()
+                        min_support=min_support
+                        layout=layout
+                        vectors=vectors
+                    />
                 })
             } else {
                 Either::Right(view! {
@@ -0,0 +1,116 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::fca::derivation::{attribute_derivation, format_set};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub premise: BitSet,
+    pub conclusion: BitSet,
+    pub support: f64,
+    pub confidence: f64,
+    pub lift: f64,
+}
+
+// The rule B1 -> B2 \ B1 between the intents of two concepts with B1 ⊂ B2.
+fn rule(
+    context: &FormalContext<String>,
+    upper: &(BitSet, BitSet),
+    lower: &(BitSet, BitSet),
+) -> Rule {
+    let objects = context.objects.len().max(1) as f64;
+    let conclusion: BitSet = lower.1.difference(&upper.1).collect();
+
+    let support = lower.0.len() as f64 / objects;
+    let confidence = lower.0.len() as f64 / upper.0.len() as f64;
+    let expected = attribute_derivation(context, &conclusion).len() as f64 / objects;
+
+    Rule {
+        premise: upper.1.clone(),
+        conclusion,
+        support,
+        confidence,
+        lift: if expected > 0.0 {
+            confidence / expected
+        } else {
+            0.0
+        },
+    }
+}
+
+fn keep(rule: &Rule, min_support: f64, min_confidence: f64) -> bool {
+    rule.support >= min_support && rule.confidence >= min_confidence
+}
+
+// Rules between each concept and its lower neighbours.
+pub fn luxenburger_basis(
+    context: &FormalContext<String>,
+    concepts: &[(BitSet, BitSet)],
+    min_support: f64,
+    min_confidence: f64,
+) -> Vec<Rule> {
+    let mut rules = Vec::new();
+
+    for upper in concepts.iter().filter(|concept| !concept.0.is_empty()) {
+        let mut below: Vec<&(BitSet, BitSet)> = concepts
+            .iter()
+            .filter(|lower| {
+                !lower.0.is_empty() && upper.1.is_subset(&lower.1) && upper.1 != lower.1
+            })
+            .collect();
+        below.sort_by_key(|lower| lower.1.len());
+
+        let mut covers: Vec<&(BitSet, BitSet)> = Vec::new();
+        for lower in below {
+            if !covers.iter().any(|cover| cover.1.is_subset(&lower.1)) {
+                covers.push(lower);
+            }
+        }
+
+        rules.extend(
+            covers
+                .into_iter()
+                .map(|lower| rule(context, upper, lower))
+                .filter(|rule| keep(rule, min_support, min_confidence)),
+        );
+    }
+    rules
+}
+
+// Rules between every pair of comparable concepts.
+pub fn all_rules(
+    context: &FormalContext<String>,
+    concepts: &[(BitSet, BitSet)],
+    min_support: f64,
+    min_confidence: f64,
+) -> Vec<Rule> {
+    let mut rules = Vec::new();
+
+    for upper in concepts.iter().filter(|concept| !concept.0.is_empty()) {
+        rules.extend(
+            concepts
+                .iter()
+                .filter(|lower| {
+                    !lower.0.is_empty() && upper.1.is_subset(&lower.1) && upper.1 != lower.1
+                })
+                .map(|lower| rule(context, upper, lower))
+                .filter(|rule| keep(rule, min_support, min_confidence)),
+        );
+    }
+    rules
+}
+
+pub fn to_csv(rules: &[Rule], attributes: &[String]) -> String {
+    let mut content = "premise;conclusion;support;confidence;lift\n".to_string();
+    for rule in rules {
+        content.push_str(&format!(
+            "{};{};{:.4};{:.4};{:.4}\n",
+            format_set(&rule.premise, attributes),
+            format_set(&rule.conclusion, attributes),
+            rule.support,
+            rule.confidence,
+            rule.lift,
+        ));
+    }
+    content
+}
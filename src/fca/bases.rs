@@ -0,0 +1,189 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::fca::implications::entails;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Basis {
+    Canonical,
+    ProperPremise,
+    D,
+    CanonicalDirect,
+}
+
+impl Basis {
+    pub const ALL: [Basis; 4] = [
+        Basis::Canonical,
+        Basis::ProperPremise,
+        Basis::D,
+        Basis::CanonicalDirect,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Basis::Canonical => "Canonical base",
+            Basis::ProperPremise => "Proper-premise base",
+            Basis::D => "D-basis",
+            Basis::CanonicalDirect => "Canonical direct basis",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Basis> {
+        Basis::ALL.into_iter().find(|n| n.name() == name)
+    }
+
+    pub fn compute(&self, context: &FormalContext<String>) -> Vec<(BitSet, BitSet)> {
+        match self {
+            Basis::Canonical => context.canonical_basis(),
+            Basis::ProperPremise => proper_premise_base(context),
+            Basis::D => d_basis(context),
+            Basis::CanonicalDirect => canonical_direct_basis(context),
+        }
+    }
+}
+
+// Berge's algorithm, one edge after the other.
+pub fn minimal_transversals(edges: &[BitSet]) -> Vec<BitSet> {
+    let mut transversals = vec![BitSet::new()];
+
+    for edge in edges {
+        let mut next = Vec::new();
+        for transversal in &transversals {
+            if !transversal.is_disjoint(edge) {
+                next.push(transversal.clone());
+            } else {
+                for n in edge {
+                    let mut extended = transversal.clone();
+                    extended.insert(n);
+                    next.push(extended);
+                }
+            }
+        }
+
+        next.sort_by_key(|transversal| transversal.len());
+        transversals = Vec::new();
+        for transversal in next {
+            if !transversals
+                .iter()
+                .any(|other| other.is_subset(&transversal))
+            {
+                transversals.push(transversal);
+            }
+        }
+    }
+    transversals
+}
+
+// The minimal sets A not containing m with m ∈ A''. A implies m iff it meets
+// M \ (g' ∪ {m}) for every object g without m.
+pub fn proper_premises(context: &FormalContext<String>, m: usize) -> Vec<BitSet> {
+    let all: BitSet = (0..context.attributes.len()).collect();
+    let edges: Vec<BitSet> = context
+        .atomic_object_derivations
+        .iter()
+        .filter(|intent| !intent.contains(m))
+        .map(|intent| {
+            let mut edge: BitSet = all.difference(intent).collect();
+            edge.remove(m);
+            edge
+        })
+        .collect();
+
+    minimal_transversals(&edges)
+}
+
+// A -> m for every proper premise A of m.
+pub fn proper_premise_base(context: &FormalContext<String>) -> Vec<(BitSet, BitSet)> {
+    (0..context.attributes.len())
+        .flat_map(|m| {
+            proper_premises(context, m)
+                .into_iter()
+                .map(move |premise| (premise, BitSet::from_iter([m])))
+        })
+        .collect()
+}
+
+// The proper-premise base with all conclusions of a premise joined, A -> A•.
+pub fn canonical_direct_basis(context: &FormalContext<String>) -> Vec<(BitSet, BitSet)> {
+    let mut basis: Vec<(BitSet, BitSet)> = Vec::new();
+
+    for (premise, conclusion) in proper_premise_base(context) {
+        if let Some(existing) = basis.iter_mut().find(|other| other.0 == premise) {
+            existing.1.union_with(&conclusion);
+        } else {
+            basis.push((premise, conclusion));
+        }
+    }
+    basis
+}
+
+// The binary part of the proper-premise base plus, for every m, the premises of m that are
+// minimal with respect to the refinement B ≪ A (every b ∈ B follows from some a ∈ A).
+pub fn d_basis(context: &FormalContext<String>) -> Vec<(BitSet, BitSet)> {
+    let base = proper_premise_base(context);
+    let hulls: Vec<BitSet> = (0..context.attributes.len())
+        .map(|a| context.index_attribute_hull(&BitSet::from_iter([a])))
+        .collect();
+
+    let refines = |left: &BitSet, right: &BitSet| {
+        left.iter()
+            .all(|b| right.iter().any(|a| hulls[a].contains(b)))
+    };
+
+    base.iter()
+        .filter(|(premise, conclusion)| {
+            premise.len() <= 1
+                || !base.iter().any(|(other, other_conclusion)| {
+                    other_conclusion == conclusion
+                        && other != premise
+                        && refines(other, premise)
+                        && !refines(premise, other)
+                })
+        })
+        .cloned()
+        .collect()
+}
+
+pub fn equivalent(left: &[(BitSet, BitSet)], right: &[(BitSet, BitSet)]) -> bool {
+    left.iter()
+        .all(|(premise, conclusion)| entails(right, premise, conclusion))
+        && right
+            .iter()
+            .all(|(premise, conclusion)| entails(left, premise, conclusion))
+}
+
+// (implications, distinct premises, total size), conclusions counted without their premise
+pub fn statistics(basis: &[(BitSet, BitSet)]) -> (usize, usize, usize) {
+    let mut premises: Vec<&BitSet> = basis.iter().map(|implication| &implication.0).collect();
+    premises.sort_by_key(|premise| premise.iter().collect::<Vec<usize>>());
+    premises.dedup();
+
+    let size = basis
+        .iter()
+        .map(|(premise, conclusion)| premise.len() + conclusion.difference(premise).count())
+        .sum();
+
+    (basis.len(), premises.len(), size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fca::generators::random_context;
+
+    #[test]
+    fn all_bases_are_equivalent_to_the_canonical_base() {
+        for seed in 0..8 {
+            let context = random_context(10, 6, 0.4, seed);
+            let canonical = context.canonical_basis();
+            for basis in Basis::ALL {
+                assert!(
+                    equivalent(&basis.compute(&context), &canonical),
+                    "{} for seed {}",
+                    basis.name(),
+                    seed
+                );
+            }
+        }
+    }
+}
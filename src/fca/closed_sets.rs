@@ -0,0 +1,80 @@
+use bit_set::BitSet;
+use odis::{FormalContext, algorithms::canonical_basis};
+
+// listing all subsets is only offered up to this many attributes
+pub const SUBSET_LIMIT: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetKind {
+    Intent,
+    PseudoIntent,
+    Neither,
+}
+
+impl SetKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SetKind::Intent => "intent",
+            SetKind::PseudoIntent => "pseudo-intent",
+            SetKind::Neither => "neither",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub set: BitSet,
+    pub closure: BitSet,
+    pub kind: SetKind,
+}
+
+// Hands the sets visited by `next_preclosure` while the canonical basis is built to `emit`,
+// in lectic order. The pseudo-intents among them are the premises of the basis.
+pub fn visit_preclosures(context: &FormalContext<String>, emit: &mut dyn FnMut(Step)) {
+    let all: BitSet = (0..context.attributes.len()).collect();
+    let mut basis = Vec::new();
+    let mut set = BitSet::new();
+
+    loop {
+        let closure = context.index_attribute_hull(&set);
+        let kind = if closure == set {
+            SetKind::Intent
+        } else {
+            basis.push((set.clone(), closure.clone()));
+            SetKind::PseudoIntent
+        };
+        emit(Step {
+            set: set.clone(),
+            closure,
+            kind,
+        });
+
+        if set == all {
+            break;
+        }
+        set = canonical_basis::next_preclosure(context, &basis, &set);
+    }
+}
+
+pub fn preclosure_steps(context: &FormalContext<String>) -> Vec<Step> {
+    let mut steps = Vec::new();
+    visit_preclosures(context, &mut |step| steps.push(step));
+    steps
+}
+
+// All subsets of the attributes in lectic order, the smallest attribute being the most significant.
+pub fn all_subsets(context: &FormalContext<String>, steps: &[Step]) -> Vec<(BitSet, SetKind)> {
+    let n = context.attributes.len();
+
+    (0..1u32 << n)
+        .map(|k| {
+            let set: BitSet = (0..n).filter(|m| k & (1 << (n - 1 - m)) != 0).collect();
+            let kind = steps
+                .iter()
+                .find(|step| step.set == set)
+                .map(|step| step.kind)
+                .unwrap_or(SetKind::Neither);
+            (set, kind)
+        })
+        .collect()
+}
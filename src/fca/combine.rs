@@ -0,0 +1,158 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+
+pub fn build_context(
+    objects: Vec<String>,
+    attributes: Vec<String>,
+    intents: Vec<BitSet>,
+) -> FormalContext<String> {
+    let mut context = FormalContext::new();
+    for attribute in attributes {
+        context.add_attribute(attribute, &BitSet::new());
+    }
+    for (object, intent) in objects.into_iter().zip(intents) {
+        context.add_object(object, &intent);
+    }
+    context
+}
+
+pub fn transpose(context: &FormalContext<String>) -> FormalContext<String> {
+    build_context(
+        context.attributes.clone(),
+        context.objects.clone(),
+        context.atomic_attribute_derivations.clone(),
+    )
+}
+
+// For every name in `left` the position of the first equal name in `right`.
+pub fn match_by_name(left: &[String], right: &[String]) -> Vec<Option<usize>> {
+    left.iter()
+        .map(|name| right.iter().position(|other| other == name))
+        .collect()
+}
+
+// The context restricted to `attributes` in that order, every one of them has to exist.
+pub fn align(
+    context: &FormalContext<String>,
+    attributes: &[String],
+) -> Result<FormalContext<String>, String> {
+    let matching = match_by_name(attributes, &context.attributes);
+    if let Some(n) = matching.iter().position(Option::is_none) {
+        return Err(format!("Missing attribute: {}", attributes[n]));
+    }
+
+    Ok(build_context(
+        context.objects.clone(),
+        attributes.to_vec(),
+        context
+            .atomic_object_derivations
+            .iter()
+            .map(|intent| {
+                matching
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, m)| intent.contains(m.unwrap()))
+                    .map(|(n, _)| n)
+                    .collect()
+            })
+            .collect(),
+    ))
+}
+
+// Names of `right` that also occur in `left`.
+pub fn clashes(left: &[String], right: &[String]) -> Vec<String> {
+    right
+        .iter()
+        .filter(|name| left.contains(name))
+        .cloned()
+        .collect()
+}
+
+// `right` with " (2)", " (3)", ... appended to every name that occurs in `left`
+// or earlier in `right`.
+pub fn suffixed(left: &[String], right: &[String]) -> Vec<String> {
+    let mut taken: Vec<String> = left.to_vec();
+    let mut result = Vec::new();
+    for name in right {
+        let mut candidate = name.clone();
+        let mut n = 2;
+        while taken.contains(&candidate) {
+            candidate = format!("{} ({})", name, n);
+            n += 1;
+        }
+        taken.push(candidate.clone());
+        result.push(candidate);
+    }
+    result
+}
+
+// `matching[g]` is the object of `right` that is placed next to object `g` of `left`.
+// Objects of `right` without partner are appended with an empty left part.
+pub fn apposition(
+    left: &FormalContext<String>,
+    right: &FormalContext<String>,
+    matching: &[Option<usize>],
+) -> FormalContext<String> {
+    let shift = left.attributes.len();
+    let shifted = |intent: &BitSet| intent.iter().map(|m| m + shift).collect::<BitSet>();
+
+    let mut objects = left.objects.clone();
+    let mut intents = Vec::new();
+    for (g, intent) in left.atomic_object_derivations.iter().enumerate() {
+        let mut intent = intent.clone();
+        if let Some(Some(h)) = matching.get(g) {
+            intent.union_with(&shifted(&right.atomic_object_derivations[*h]));
+        }
+        intents.push(intent);
+    }
+
+    let partners: BitSet = matching.iter().flatten().copied().collect();
+    for h in 0..right.objects.len() {
+        if !partners.contains(h) {
+            objects.push(right.objects[h].clone());
+            intents.push(shifted(&right.atomic_object_derivations[h]));
+        }
+    }
+
+    let mut attributes = left.attributes.clone();
+    attributes.extend(right.attributes.iter().cloned());
+
+    build_context(objects, attributes, intents)
+}
+
+// `matching[m]` is the attribute of `bottom` that is identified with attribute `m` of `top`.
+// Attributes of `bottom` without partner are appended.
+pub fn subposition(
+    top: &FormalContext<String>,
+    bottom: &FormalContext<String>,
+    matching: &[Option<usize>],
+) -> FormalContext<String> {
+    transpose(&apposition(&transpose(top), &transpose(bottom), matching))
+}
+
+pub fn subcontext(
+    context: &FormalContext<String>,
+    objects: &BitSet,
+    attributes: &BitSet,
+) -> FormalContext<String> {
+    let positions: Vec<usize> = attributes.iter().collect();
+
+    build_context(
+        objects.iter().map(|g| context.objects[g].clone()).collect(),
+        attributes
+            .iter()
+            .map(|m| context.attributes[m].clone())
+            .collect(),
+        objects
+            .iter()
+            .map(|g| {
+                positions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, m)| context.atomic_object_derivations[g].contains(**m))
+                    .map(|(n, _)| n)
+                    .collect()
+            })
+            .collect(),
+    )
+}
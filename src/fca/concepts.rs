@@ -0,0 +1,207 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::fca::derivation::{attribute_derivation, object_derivation};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Algorithm {
+    NextClosure,
+    CloseByOne,
+    FCbO,
+    InClose,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 4] = [
+        Algorithm::NextClosure,
+        Algorithm::CloseByOne,
+        Algorithm::FCbO,
+        Algorithm::InClose,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::NextClosure => "NextClosure",
+            Algorithm::CloseByOne => "Close-by-One",
+            Algorithm::FCbO => "FCbO",
+            Algorithm::InClose => "In-Close",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Algorithm> {
+        Algorithm::ALL.into_iter().find(|n| n.name() == name)
+    }
+
+    // hands every concept to `emit` as soon as it is found, only the worker enumerates
+    #[allow(dead_code)]
+    pub fn enumerate(
+        &self,
+        context: &FormalContext<String>,
+        emit: &mut dyn FnMut((BitSet, BitSet)),
+    ) {
+        match self {
+            Algorithm::NextClosure => next_closure(context, emit),
+            Algorithm::CloseByOne => close_by_one(context, emit),
+            Algorithm::FCbO => context.fcbo_index_concepts().for_each(emit),
+            Algorithm::InClose => in_close(context, emit),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn compute(&self, context: &FormalContext<String>) -> Vec<(BitSet, BitSet)> {
+        let mut result = Vec::new();
+        self.enumerate(context, &mut |concept| result.push(concept));
+        result
+    }
+}
+
+fn prefix(set: &BitSet, end: usize) -> BitSet {
+    set.iter().take_while(|n| *n < end).collect()
+}
+
+pub fn next_closure(context: &FormalContext<String>, emit: &mut dyn FnMut((BitSet, BitSet))) {
+    let mut intent = context.index_attribute_hull(&BitSet::new());
+
+    'outer: loop {
+        emit((attribute_derivation(context, &intent), intent.clone()));
+
+        for m in (0..context.attributes.len()).rev() {
+            if intent.contains(m) {
+                continue;
+            }
+            let mut candidate = prefix(&intent, m);
+            candidate.insert(m);
+            let candidate = context.index_attribute_hull(&candidate);
+
+            if prefix(&candidate, m) == prefix(&intent, m) {
+                intent = candidate;
+                continue 'outer;
+            }
+        }
+        break;
+    }
+}
+
+pub fn close_by_one(context: &FormalContext<String>, emit: &mut dyn FnMut((BitSet, BitSet))) {
+    fn generate(
+        context: &FormalContext<String>,
+        extent: BitSet,
+        intent: BitSet,
+        start: usize,
+        emit: &mut dyn FnMut((BitSet, BitSet)),
+    ) {
+        emit((extent.clone(), intent.clone()));
+
+        for m in start..context.attributes.len() {
+            if intent.contains(m) {
+                continue;
+            }
+            let new_extent: BitSet = extent
+                .intersection(&context.atomic_attribute_derivations[m])
+                .collect();
+            let new_intent = object_derivation(context, &new_extent);
+
+            if prefix(&new_intent, m) == prefix(&intent, m) {
+                generate(context, new_extent, new_intent, m + 1, emit);
+            }
+        }
+    }
+
+    let extent: BitSet = (0..context.objects.len()).collect();
+    let intent = object_derivation(context, &extent);
+    generate(context, extent, intent, 0, emit);
+}
+
+// Like Close-by-One, but the canonicity test runs on the new extent before its intent is built.
+pub fn in_close(context: &FormalContext<String>, emit: &mut dyn FnMut((BitSet, BitSet))) {
+    fn generate(
+        context: &FormalContext<String>,
+        extent: BitSet,
+        intent: BitSet,
+        start: usize,
+        emit: &mut dyn FnMut((BitSet, BitSet)),
+    ) {
+        emit((extent.clone(), intent.clone()));
+
+        for m in start..context.attributes.len() {
+            if intent.contains(m) {
+                continue;
+            }
+            let new_extent: BitSet = extent
+                .intersection(&context.atomic_attribute_derivations[m])
+                .collect();
+
+            let canonical = (0..m).all(|n| {
+                intent.contains(n)
+                    || !new_extent.is_subset(&context.atomic_attribute_derivations[n])
+            });
+            if !canonical {
+                continue;
+            }
+
+            let mut new_intent = intent.clone();
+            new_intent.insert(m);
+            for n in (m + 1)..context.attributes.len() {
+                if new_extent.is_subset(&context.atomic_attribute_derivations[n]) {
+                    new_intent.insert(n);
+                }
+            }
+            generate(context, new_extent, new_intent, m + 1, emit);
+        }
+    }
+
+    let extent: BitSet = (0..context.objects.len()).collect();
+    let intent = object_derivation(context, &extent);
+    generate(context, extent, intent, 0, emit);
+}
+
+// Whether both lists contain the same concepts, regardless of order. Only the worker
+// compares algorithms.
+#[allow(dead_code)]
+pub fn same_concepts(left: &[(BitSet, BitSet)], right: &[(BitSet, BitSet)]) -> bool {
+    let sorted = |concepts: &[(BitSet, BitSet)]| {
+        let mut intents: Vec<Vec<usize>> = concepts
+            .iter()
+            .map(|concept| concept.1.iter().collect())
+            .collect();
+        intents.sort();
+        intents
+    };
+    sorted(left) == sorted(right)
+}
+
+// The smallest extent size of a frequent concept.
+pub fn min_count(objects: usize, min_support: f64) -> usize {
+    (min_support * objects as f64).ceil() as usize
+}
+
+// Positions of the frequent concepts, whose extent holds at least `min_support` of all objects.
+pub fn iceberg(concepts: &[(BitSet, BitSet)], objects: usize, min_support: f64) -> BitSet {
+    let min_count = min_count(objects, min_support);
+    (0..concepts.len())
+        .filter(|n| concepts[*n].0.len() >= min_count)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fca::generators::{contranominal_scale, random_context};
+
+    #[test]
+    fn all_algorithms_find_the_same_concepts() {
+        let mut contexts: Vec<FormalContext<String>> = (0..8)
+            .map(|seed| random_context(10, 7, 0.4, seed))
+            .collect();
+        contexts.push(contranominal_scale(4));
+
+        for context in contexts {
+            let expected = Algorithm::FCbO.compute(&context);
+            for algorithm in Algorithm::ALL {
+                let concepts = algorithm.compute(&context);
+                assert_eq!(concepts.len(), expected.len(), "{}", algorithm.name());
+                assert!(same_concepts(&concepts, &expected), "{}", algorithm.name());
+            }
+        }
+    }
+}
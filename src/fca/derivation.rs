@@ -0,0 +1,43 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+
+// the objects having all of the given attributes
+pub fn attribute_derivation(context: &FormalContext<String>, attributes: &BitSet) -> BitSet {
+    let mut extent: BitSet = (0..context.objects.len()).collect();
+    for m in attributes {
+        extent.intersect_with(&context.atomic_attribute_derivations[m]);
+    }
+    extent
+}
+
+// the attributes shared by all of the given objects
+pub fn object_derivation(context: &FormalContext<String>, objects: &BitSet) -> BitSet {
+    let mut intent: BitSet = (0..context.attributes.len()).collect();
+    for g in objects {
+        intent.intersect_with(&context.atomic_object_derivations[g]);
+    }
+    intent
+}
+
+pub fn object_hull(context: &FormalContext<String>, objects: &BitSet) -> BitSet {
+    attribute_derivation(context, &object_derivation(context, objects))
+}
+
+pub fn format_set(set: &BitSet, names: &[String]) -> String {
+    let names: Vec<&str> = set.iter().map(|n| names[n].as_str()).collect();
+    format!("{{{}}}", names.join(", "))
+}
+
+// Indices separated by spaces, for the worker messages and the exploration sessions.
+pub fn encode_set(set: &BitSet) -> String {
+    set.iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+pub fn decode_set(text: &str) -> Result<BitSet, String> {
+    text.split_whitespace()
+        .map(|n| n.parse().map_err(|_| format!("Invalid index: {}", n)))
+        .collect()
+}
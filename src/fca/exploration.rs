@@ -0,0 +1,550 @@
+use bit_set::BitSet;
+use odis::{FormalContext, algorithms::canonical_basis};
+
+use crate::fca::{
+    derivation::format_set,
+    implications::{closure, counterexamples, entails, format_implication},
+};
+
+// One answered question, together with the state needed to take the answer back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    pub premise: BitSet,
+    pub conclusion: BitSet,
+    // name of the object given as counterexample, `None` if the implication was accepted
+    pub counterexample: Option<String>,
+    // (intent, unknown attributes) of the counterexample when it was given, `None` for
+    // accepted implications and sessions saved before these were kept
+    pub counterexample_attributes: Option<(BitSet, BitSet)>,
+    pub basis_len: usize,
+    pub num_objects: usize,
+    // attributes added later are taken back with the answer, `None` for sessions saved
+    // before these were kept
+    pub num_attributes: Option<usize>,
+    // milliseconds since the epoch
+    pub time: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum State {
+    AskImplication { premise: BitSet, conclusion: BitSet },
+    // the implication was rejected, an object refuting it has to be added
+    AwaitCounterexample { premise: BitSet, conclusion: BitSet },
+    Finished,
+}
+
+// Attribute exploration independent of any frontend. The context is not owned, every step
+// takes the current one, so that counterexamples can be added wherever the context lives.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exploration {
+    // implications known in advance, kept apart from the confirmed ones
+    pub background: Vec<(BitSet, BitSet)>,
+    pub basis: Vec<(BitSet, BitSet)>,
+    // the set the current or next question is asked about
+    pub candidate: BitSet,
+    pub history: Vec<Answer>,
+    pub state: State,
+}
+
+impl Exploration {
+    // Starts at the closure of the empty set under the background knowledge, which has to
+    // hold in the context.
+    pub fn new(
+        context: &FormalContext<String>,
+        background: Vec<(BitSet, BitSet)>,
+    ) -> Result<Exploration, String> {
+        if let Some(implication) = background
+            .iter()
+            .find(|(premise, conclusion)| !counterexamples(context, premise, conclusion).is_empty())
+        {
+            return Err(format!(
+                "Not valid in the context: {}",
+                format_implication(implication, &context.attributes),
+            ));
+        }
+
+        let candidate = closure(&background, &BitSet::new());
+        Ok(Exploration::resume(
+            context,
+            background,
+            Vec::new(),
+            candidate,
+            Vec::new(),
+        ))
+    }
+
+    pub fn resume(
+        context: &FormalContext<String>,
+        background: Vec<(BitSet, BitSet)>,
+        basis: Vec<(BitSet, BitSet)>,
+        candidate: BitSet,
+        history: Vec<Answer>,
+    ) -> Exploration {
+        let mut exploration = Exploration {
+            background,
+            basis,
+            candidate,
+            history,
+            state: State::Finished,
+        };
+        exploration.advance(context);
+        exploration
+    }
+
+    pub fn known(&self) -> Vec<(BitSet, BitSet)> {
+        [self.background.clone(), self.basis.clone()].concat()
+    }
+
+    // The implication asked about, while there is one.
+    pub fn question(&self) -> Option<(BitSet, BitSet)> {
+        match &self.state {
+            State::AskImplication {
+                premise,
+                conclusion,
+            }
+            | State::AwaitCounterexample {
+                premise,
+                conclusion,
+            } => Some((premise.clone(), conclusion.clone())),
+            State::Finished => None,
+        }
+    }
+
+    // Walks through the preclosed sets from the candidate on until one leads to an
+    // implication that does not follow from the known ones.
+    pub fn advance(&mut self, context: &FormalContext<String>) {
+        let all: BitSet = (0..context.attributes.len()).collect();
+
+        while self.candidate != all {
+            let hull = context.index_attribute_hull(&self.candidate);
+            let known = self.known();
+
+            if self.candidate != hull && !entails(&known, &self.candidate, &hull) {
+                self.state = State::AskImplication {
+                    premise: self.candidate.clone(),
+                    conclusion: hull,
+                };
+                return;
+            }
+            self.candidate = canonical_basis::next_preclosure(context, &known, &self.candidate);
+        }
+        self.state = State::Finished;
+    }
+
+    pub fn accept(&mut self, context: &FormalContext<String>, time: f64) {
+        let State::AskImplication {
+            premise,
+            conclusion,
+        } = self.state.clone()
+        else {
+            return;
+        };
+
+        self.history.push(Answer {
+            premise: premise.clone(),
+            conclusion: conclusion.clone(),
+            counterexample: None,
+            counterexample_attributes: None,
+            basis_len: self.basis.len(),
+            num_objects: context.objects.len(),
+            num_attributes: Some(context.attributes.len()),
+            time,
+        });
+        self.basis.push((premise, conclusion));
+        self.advance(context);
+    }
+
+    pub fn reject(&mut self) {
+        if let State::AskImplication {
+            premise,
+            conclusion,
+        } = self.state.clone()
+        {
+            self.state = State::AwaitCounterexample {
+                premise,
+                conclusion,
+            };
+        }
+    }
+
+    // Why an object `name` having `intent` for sure and possibly `unknown` does not refute
+    // the rejected implication, if it does not.
+    pub fn counterexample_error(
+        &self,
+        context: &FormalContext<String>,
+        name: &str,
+        intent: &BitSet,
+        unknown: &BitSet,
+    ) -> Option<String> {
+        let (premise, conclusion) = self.question()?;
+        let mut possible = intent.clone();
+        possible.union_with(unknown);
+
+        if name.trim().is_empty() {
+            return Some("Enter a name for the counterexample.".to_string());
+        }
+        if context.objects.iter().any(|object| object == name.trim()) {
+            return Some(format!("The name {} is already taken.", name.trim()));
+        }
+        if !premise.is_subset(intent) {
+            let missing: BitSet = premise.difference(intent).collect();
+            return Some(format!(
+                "The counterexample must certainly have all of the premise, missing: {}",
+                format_set(&missing, &context.attributes),
+            ));
+        }
+        if conclusion.is_subset(&possible) {
+            return Some(
+                "The counterexample must certainly miss at least one element of the conclusion."
+                    .to_string(),
+            );
+        }
+        if let Some(implication) = self.known().iter().find(|(premise, conclusion)| {
+            premise.is_subset(intent) && !conclusion.is_subset(&possible)
+        }) {
+            return Some(format!(
+                "The counterexample violates the known implication {}",
+                format_implication(implication, &context.attributes),
+            ));
+        }
+        None
+    }
+
+    // To be called once the counterexample `name` has been added as last object of `context`,
+    // with the certain attributes `intent` and the `unknown` ones.
+    pub fn counterexample_added(
+        &mut self,
+        context: &FormalContext<String>,
+        name: String,
+        intent: &BitSet,
+        unknown: &BitSet,
+        time: f64,
+    ) {
+        let State::AwaitCounterexample {
+            premise,
+            conclusion,
+        } = self.state.clone()
+        else {
+            return;
+        };
+
+        self.history.push(Answer {
+            premise,
+            conclusion,
+            counterexample: Some(name),
+            counterexample_attributes: Some((intent.clone(), unknown.clone())),
+            basis_len: self.basis.len(),
+            num_objects: context.objects.len() - 1,
+            num_attributes: Some(context.attributes.len()),
+            time,
+        });
+        self.advance(context);
+    }
+
+    // To be called once an attribute has been added to `context`. The confirmed implications
+    // still hold since the old incidences are unchanged, but sets with the new attribute may
+    // be pseudo-closed, so the exploration starts over from the closure of the empty set,
+    // skipping everything the known implications answer.
+    pub fn attribute_added(&mut self, context: &FormalContext<String>) {
+        self.candidate = closure(&self.known(), &BitSet::new());
+        self.advance(context);
+    }
+
+    // Asks the question of `history[index]` again, forgetting it and all later answers.
+    // Returns the numbers of objects and attributes the context had back then, later ones
+    // have to be removed.
+    pub fn revert(&mut self, index: usize) -> (usize, Option<usize>) {
+        let answer = self.history[index].clone();
+
+        self.basis.truncate(answer.basis_len);
+        self.history.truncate(index);
+        self.candidate = answer.premise.clone();
+        self.state = State::AskImplication {
+            premise: answer.premise,
+            conclusion: answer.conclusion,
+        };
+        (answer.num_objects, answer.num_attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fca::{
+        bases::equivalent,
+        combine::{align, build_context, subcontext},
+        generators::random_context,
+    };
+
+    fn set(elements: &[usize]) -> BitSet {
+        elements.iter().copied().collect()
+    }
+
+    // The column of attribute `m` of `reference` for the objects of `context`, which all come
+    // from the reference.
+    fn extent_in(
+        context: &FormalContext<String>,
+        reference: &FormalContext<String>,
+        m: usize,
+    ) -> BitSet {
+        (0..context.objects.len())
+            .filter(|g| {
+                let name = context.objects[*g].trim_end_matches(" (reference)");
+                let h = reference
+                    .objects
+                    .iter()
+                    .position(|other| other == name)
+                    .unwrap();
+                reference.atomic_object_derivations[h].contains(m)
+            })
+            .collect()
+    }
+
+    // Answers every question from `reference`, which has the same attributes as `context`.
+    fn explore(
+        context: &mut FormalContext<String>,
+        reference: &FormalContext<String>,
+        exploration: &mut Exploration,
+    ) -> usize {
+        let mut questions = 0;
+
+        while let Some((premise, conclusion)) = exploration.question() {
+            questions += 1;
+            match counterexamples(reference, &premise, &conclusion)
+                .iter()
+                .next()
+            {
+                Some(g) => {
+                    exploration.reject();
+                    let intent = &reference.atomic_object_derivations[g];
+                    let name = format!("{} (reference)", reference.objects[g]);
+                    assert_eq!(
+                        exploration.counterexample_error(context, &name, intent, &BitSet::new()),
+                        None
+                    );
+                    context.add_object(name.clone(), intent);
+                    exploration.counterexample_added(context, name, intent, &BitSet::new(), 0.0);
+                }
+                None => exploration.accept(context, 0.0),
+            }
+        }
+        questions
+    }
+
+    #[test]
+    fn accepting_everything_yields_the_canonical_basis() {
+        for seed in 0..20 {
+            let mut context = random_context(8, 6, 0.4, seed);
+            let reference = context.clone();
+            let mut exploration = Exploration::new(&context, Vec::new()).unwrap();
+
+            let questions = explore(&mut context, &reference, &mut exploration);
+
+            assert_eq!(exploration.state, State::Finished);
+            assert_eq!(exploration.basis, reference.canonical_basis());
+            assert_eq!(questions, exploration.basis.len());
+            assert_eq!(context.objects.len(), reference.objects.len());
+        }
+    }
+
+    #[test]
+    fn counterexamples_from_a_reference_complete_the_context() {
+        for seed in 0..20 {
+            let reference = random_context(12, 6, 0.4, seed);
+            let mut context = subcontext(&reference, &set(&[0, 1, 2]), &(0..6).collect());
+            let mut exploration = Exploration::new(&context, Vec::new()).unwrap();
+
+            explore(&mut context, &reference, &mut exploration);
+
+            assert_eq!(exploration.state, State::Finished);
+            assert!(equivalent(&exploration.basis, &reference.canonical_basis()));
+            assert_eq!(
+                exploration.history.len(),
+                exploration.basis.len() + context.objects.len() - 3
+            );
+        }
+    }
+
+    #[test]
+    fn background_knowledge_is_never_asked_about() {
+        for seed in 0..20 {
+            let mut context = random_context(8, 6, 0.4, seed);
+            let reference = context.clone();
+            let canonical = reference.canonical_basis();
+            let background: Vec<_> = canonical.iter().step_by(2).cloned().collect();
+            let mut exploration = Exploration::new(&context, background.clone()).unwrap();
+
+            explore(&mut context, &reference, &mut exploration);
+
+            for answer in &exploration.history {
+                assert!(!entails(&background, &answer.premise, &answer.conclusion));
+            }
+            assert!(equivalent(&exploration.known(), &canonical));
+            assert_eq!(exploration.background, background);
+        }
+    }
+
+    #[test]
+    fn invalid_background_is_rejected() {
+        let context = build_context(
+            vec!["g".to_string()],
+            vec!["a".to_string(), "b".to_string()],
+            vec![set(&[0])],
+        );
+
+        assert!(Exploration::new(&context, vec![(set(&[0]), set(&[1]))]).is_err());
+        assert!(Exploration::new(&context, vec![(set(&[1]), set(&[0]))]).is_ok());
+    }
+
+    #[test]
+    fn counterexamples_are_validated() {
+        // every object has a, so the first question is {} -> {a}
+        let mut context = build_context(
+            vec!["g".to_string(), "h".to_string()],
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            vec![set(&[0, 1]), set(&[0, 2])],
+        );
+        let mut exploration = Exploration::new(&context, Vec::new()).unwrap();
+        assert_eq!(
+            exploration.state,
+            State::AskImplication {
+                premise: set(&[]),
+                conclusion: set(&[0]),
+            }
+        );
+
+        exploration.reject();
+        let error = |name: &str, intent: &[usize], unknown: &[usize]| {
+            exploration.counterexample_error(&context, name, &set(intent), &set(unknown))
+        };
+        assert!(error(" ", &[1], &[]).is_some());
+        assert!(error("g", &[1], &[]).is_some());
+        assert!(error("k", &[0, 1], &[]).is_some());
+        assert!(error("k", &[1], &[0]).is_some());
+        assert_eq!(error("k", &[1], &[2]), None);
+        assert_eq!(error("k", &[], &[]), None);
+
+        context.add_object("k".to_string(), &set(&[1]));
+        exploration.counterexample_added(&context, "k".to_string(), &set(&[1]), &set(&[2]), 0.0);
+        assert_eq!(exploration.history[0].counterexample, Some("k".to_string()));
+        assert_eq!(
+            exploration.history[0].counterexample_attributes,
+            Some((set(&[1]), set(&[2])))
+        );
+        assert_eq!(exploration.history[0].num_objects, 2);
+        assert_ne!(exploration.question(), Some((set(&[]), set(&[0]))));
+    }
+
+    #[test]
+    fn attributes_can_be_added_midway() {
+        for seed in 0..20 {
+            let reference = random_context(12, 6, 0.4, seed);
+            let mut context = subcontext(&reference, &set(&[0, 1, 2, 3]), &(0..5).collect());
+            let mut exploration = Exploration::new(&context, Vec::new()).unwrap();
+
+            // answer about half of the questions before the last attribute shows up
+            let attributes: Vec<String> = context.attributes.clone();
+            let partial = align(&reference, &attributes).unwrap();
+            for _ in 0..3 {
+                let Some((premise, conclusion)) = exploration.question() else {
+                    break;
+                };
+                match counterexamples(&partial, &premise, &conclusion)
+                    .iter()
+                    .next()
+                {
+                    Some(g) => {
+                        exploration.reject();
+                        let name = format!("{} (reference)", partial.objects[g]);
+                        let intent = &partial.atomic_object_derivations[g];
+                        context.add_object(name.clone(), intent);
+                        exploration.counterexample_added(
+                            &context,
+                            name,
+                            intent,
+                            &BitSet::new(),
+                            0.0,
+                        );
+                    }
+                    None => exploration.accept(&context, 0.0),
+                }
+            }
+
+            let extent = extent_in(&context, &reference, 5);
+            context.add_attribute(reference.attributes[5].clone(), &extent);
+            exploration.attribute_added(&context);
+
+            explore(&mut context, &reference, &mut exploration);
+            assert_eq!(exploration.state, State::Finished);
+            assert!(equivalent(
+                &exploration.known(),
+                &reference.canonical_basis()
+            ));
+        }
+    }
+
+    #[test]
+    fn reverting_restores_the_question() {
+        let mut context = random_context(10, 6, 0.4, 7);
+        let reference = context.clone();
+        let mut exploration = Exploration::new(&context, Vec::new()).unwrap();
+        let first = exploration.question().unwrap();
+
+        explore(&mut context, &reference, &mut exploration);
+        assert!(exploration.basis.len() > 1);
+
+        let (num_objects, num_attributes) = exploration.revert(1);
+        assert_eq!(num_objects, context.objects.len());
+        assert_eq!(num_attributes, Some(6));
+        assert_eq!(exploration.basis.len(), 1);
+        assert_eq!(exploration.history.len(), 1);
+
+        assert_eq!(exploration.revert(0).0, context.objects.len());
+        assert_eq!(exploration.question(), Some(first));
+        assert!(exploration.basis.is_empty());
+
+        explore(&mut context, &reference, &mut exploration);
+        assert_eq!(exploration.basis, reference.canonical_basis());
+    }
+
+    #[test]
+    fn reverting_past_an_added_attribute_takes_it_back() {
+        for seed in 0..20 {
+            let reference = random_context(12, 6, 0.4, seed);
+            let mut context = subcontext(&reference, &set(&[0, 1, 2, 3]), &(0..5).collect());
+            let partial = align(&reference, &context.attributes.clone()).unwrap();
+            let mut exploration = Exploration::new(&context, Vec::new()).unwrap();
+
+            explore(&mut context, &partial, &mut exploration);
+            if exploration.history.is_empty() {
+                continue;
+            }
+            let extent = extent_in(&context, &reference, 5);
+            context.add_attribute(reference.attributes[5].clone(), &extent);
+            exploration.attribute_added(&context);
+            explore(&mut context, &reference, &mut exploration);
+
+            // back to the first question, asked before the attribute was there
+            let (num_objects, num_attributes) = exploration.revert(0);
+            assert_eq!(num_attributes, Some(5));
+            while context.objects.len() > num_objects {
+                context.remove_object(context.objects.len() - 1);
+            }
+            context.remove_attribute(5);
+            exploration.advance(&context);
+
+            // adding the attribute again has to bring back the sets that involve it
+            explore(&mut context, &partial, &mut exploration);
+            let extent = extent_in(&context, &reference, 5);
+            context.add_attribute(reference.attributes[5].clone(), &extent);
+            exploration.attribute_added(&context);
+            explore(&mut context, &reference, &mut exploration);
+
+            assert_eq!(exploration.state, State::Finished);
+            assert!(equivalent(
+                &exploration.known(),
+                &reference.canonical_basis()
+            ));
+        }
+    }
+}
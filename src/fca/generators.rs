@@ -0,0 +1,78 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::fca::combine::build_context;
+
+// SplitMix64, small enough to not pull in a dependency and reproducible for a given seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn names(prefix: &str, n: usize) -> Vec<String> {
+    (0..n).map(|k| format!("{} {}", prefix, k)).collect()
+}
+
+fn scale(n: usize, incident: impl Fn(usize, usize) -> bool) -> FormalContext<String> {
+    build_context(
+        names("Object", n),
+        names("Attribute", n),
+        (0..n)
+            .map(|g| (0..n).filter(|m| incident(g, *m)).collect())
+            .collect(),
+    )
+}
+
+pub fn random_context(
+    objects: usize,
+    attributes: usize,
+    density: f64,
+    seed: u64,
+) -> FormalContext<String> {
+    let mut rng = Rng::new(seed);
+    let intents = (0..objects)
+        .map(|_| {
+            (0..attributes)
+                .filter(|_| rng.next_f64() < density)
+                .collect::<BitSet>()
+        })
+        .collect();
+
+    build_context(
+        names("Object", objects),
+        names("Attribute", attributes),
+        intents,
+    )
+}
+
+// 2^n concepts
+pub fn contranominal_scale(n: usize) -> FormalContext<String> {
+    scale(n, |g, m| g != m)
+}
+
+// n concepts, forming a chain
+pub fn ordinal_scale(n: usize) -> FormalContext<String> {
+    scale(n, |g, m| g <= m)
+}
+
+// n + 2 concepts for n >= 2
+pub fn nominal_scale(n: usize) -> FormalContext<String> {
+    scale(n, |g, m| g == m)
+}
@@ -0,0 +1,90 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+
+// The smallest superset of `set` respecting all implications.
+pub fn closure(implications: &[(BitSet, BitSet)], set: &BitSet) -> BitSet {
+    let mut result = set.clone();
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+        for (premise, conclusion) in implications {
+            if premise.is_subset(&result) && !conclusion.is_subset(&result) {
+                result.union_with(conclusion);
+                changed = true;
+            }
+        }
+    }
+    result
+}
+
+pub fn entails(implications: &[(BitSet, BitSet)], premise: &BitSet, conclusion: &BitSet) -> bool {
+    conclusion.is_subset(&closure(implications, premise))
+}
+
+// The objects having all attributes of the premise but not all of the conclusion.
+pub fn counterexamples(
+    context: &FormalContext<String>,
+    premise: &BitSet,
+    conclusion: &BitSet,
+) -> BitSet {
+    (0..context.objects.len())
+        .filter(|g| {
+            let intent = &context.atomic_object_derivations[*g];
+            premise.is_subset(intent) && !conclusion.is_subset(intent)
+        })
+        .collect()
+}
+
+// Comma separated names, surrounding braces are optional.
+pub fn parse_set(text: &str, names: &[String]) -> Result<BitSet, String> {
+    let text = text.trim().trim_start_matches('{').trim_end_matches('}');
+    let mut set = BitSet::new();
+
+    for name in text
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let Some(n) = names.iter().position(|other| other == name) else {
+            return Err(format!("Unknown name: {}", name));
+        };
+        set.insert(n);
+    }
+    Ok(set)
+}
+
+// "a, b -> c", the arrow may also be written as "=>" or "→"
+pub fn parse_implication(text: &str, names: &[String]) -> Result<(BitSet, BitSet), String> {
+    let Some((premise, conclusion)) = ["->", "=>", "→"]
+        .iter()
+        .find_map(|arrow| text.split_once(arrow))
+    else {
+        return Err(format!("Missing arrow in: {}", text));
+    };
+    Ok((parse_set(premise, names)?, parse_set(conclusion, names)?))
+}
+
+// One implication per line, empty lines are skipped.
+pub fn parse_implications(text: &str, names: &[String]) -> Result<Vec<(BitSet, BitSet)>, String> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_implication(line, names))
+        .collect()
+}
+
+// The inverse of `parse_implication`, attributes of the premise are left out of the conclusion.
+pub fn format_implication(implication: &(BitSet, BitSet), names: &[String]) -> String {
+    let (premise, conclusion) = implication;
+    let join = |set: &BitSet| {
+        set.iter()
+            .map(|n| names[n].clone())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    format!(
+        "{} -> {}",
+        join(premise),
+        join(&conclusion.difference(premise).collect())
+    )
+}
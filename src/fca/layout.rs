@@ -0,0 +1,327 @@
+use bit_set::BitSet;
+
+pub const FORCE_ITERATIONS: usize = 300;
+pub const OPTIMISE_ROUNDS: usize = 5;
+// changes of a single attribute vector tried by the local search
+const STEPS: [f64; 6] = [-2.0, -1.0, -0.5, 0.5, 1.0, 2.0];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
+    Layered,
+    Additive,
+    ForceDirected,
+    Hybrid,
+}
+
+impl Layout {
+    pub const ALL: [Layout; 4] = [
+        Layout::Layered,
+        Layout::Additive,
+        Layout::ForceDirected,
+        Layout::Hybrid,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layout::Layered => "Layered",
+            Layout::Additive => "Additive",
+            Layout::ForceDirected => "Force-directed",
+            Layout::Hybrid => "Additive and force-directed",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Layout> {
+        Layout::ALL.into_iter().find(|n| n.name() == name)
+    }
+}
+
+// Edges as (upper, lower) concept, the upper one has the smaller intent.
+pub fn oriented(intents: &[BitSet], edges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    edges
+        .iter()
+        .map(|&(a, b)| {
+            if intents[a].len() < intents[b].len() {
+                (a, b)
+            } else {
+                (b, a)
+            }
+        })
+        .collect()
+}
+
+// Length of the longest downward path from a maximal concept.
+pub fn levels(intents: &[BitSet], edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..intents.len()).collect();
+    order.sort_by_key(|&n| intents[n].len());
+
+    let mut below = vec![Vec::new(); intents.len()];
+    for (upper, lower) in oriented(intents, edges) {
+        below[upper].push(lower);
+    }
+
+    let mut levels = vec![0; intents.len()];
+    for upper in order {
+        for &lower in &below[upper] {
+            levels[lower] = levels[lower].max(levels[upper] + 1);
+        }
+    }
+    levels
+}
+
+// Attribute vectors spread symmetrically around zero.
+pub fn attribute_vectors(attributes: usize) -> Vec<f64> {
+    (0..attributes)
+        .map(|m| m as f64 - (attributes as f64 - 1.0) / 2.0)
+        .collect()
+}
+
+// Every concept is placed at the sum of the vectors of its intent.
+pub fn additive(intents: &[BitSet], vectors: &[f64]) -> Vec<f64> {
+    intents
+        .iter()
+        .map(|intent| intent.iter().map(|m| vectors[m]).sum())
+        .collect()
+}
+
+// Concepts of the same level side by side, centred.
+fn spread(levels: &[usize]) -> Vec<f64> {
+    let mut count = vec![0; levels.iter().max().map_or(0, |l| l + 1)];
+    for &level in levels {
+        count[level] += 1;
+    }
+
+    let mut seen = vec![0; count.len()];
+    levels
+        .iter()
+        .map(|&level| {
+            let x = seen[level] as f64 - (count[level] as f64 - 1.0) / 2.0;
+            seen[level] += 1;
+            x
+        })
+        .collect()
+}
+
+// Springs along the edges and repulsion within a level, only the horizontal positions
+// move so the vertical order of the lattice is kept.
+pub fn force_directed(
+    xs: &[f64],
+    levels: &[usize],
+    edges: &[(usize, usize)],
+    iterations: usize,
+) -> Vec<f64> {
+    let mut xs = xs.to_vec();
+    let n = xs.len();
+
+    for iteration in 0..iterations {
+        let temperature = 1.0 - iteration as f64 / iterations as f64;
+        let mut forces = vec![0.0; n];
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                if levels[i] != levels[j] {
+                    continue;
+                }
+                let dx = xs[j] - xs[i];
+                // ties are broken by index
+                let distance = dx.abs().max(0.01);
+                let push = 1.0 / distance;
+                forces[i] -= push * if dx >= 0.0 { 1.0 } else { -1.0 };
+                forces[j] += push * if dx >= 0.0 { 1.0 } else { -1.0 };
+            }
+        }
+        for &(a, b) in edges {
+            let dx = xs[b] - xs[a];
+            let dy = levels[b] as f64 - levels[a] as f64;
+            let pull = dx * (dx * dx + dy * dy).sqrt();
+            forces[a] += pull;
+            forces[b] -= pull;
+        }
+
+        for (x, force) in xs.iter_mut().zip(&forces) {
+            *x += (force * 0.1).clamp(-temperature, temperature);
+        }
+    }
+    xs
+}
+
+// Positions shifted to start at zero.
+fn normalized(positions: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    let x_min = positions.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+    let y_min = positions.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    positions
+        .into_iter()
+        .map(|(x, y)| (x - x_min, y - y_min))
+        .collect()
+}
+
+// Positions of all concepts, `layered` are the ones computed by odis and
+// `edges` join concept indices.
+pub fn positions(
+    layout: Layout,
+    intents: &[BitSet],
+    vectors: &[f64],
+    edges: &[(usize, usize)],
+    layered: &[(f64, f64)],
+) -> Vec<(f64, f64)> {
+    if layout == Layout::Layered {
+        return normalized(layered.to_vec());
+    }
+
+    let levels = levels(intents, edges);
+    let xs = match layout {
+        Layout::Additive => additive(intents, vectors),
+        Layout::ForceDirected => force_directed(&spread(&levels), &levels, edges, FORCE_ITERATIONS),
+        _ => force_directed(
+            &additive(intents, vectors),
+            &levels,
+            edges,
+            FORCE_ITERATIONS,
+        ),
+    };
+
+    normalized(
+        xs.into_iter()
+            .zip(levels)
+            .map(|(x, level)| (x, level as f64))
+            .collect(),
+    )
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metrics {
+    pub crossings: usize,
+    pub length_variance: f64,
+    // pairs of nodes closer than their diameter
+    pub overlaps: usize,
+    // nodes lying on an edge they do not belong to
+    pub conflicts: usize,
+}
+
+// > 0 if `c` is left of the line from `a` to `b`
+fn orientation(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+// Only proper crossings count, edges meeting in a node do not cross.
+fn crosses(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)) -> bool {
+    orientation(a, b, c) * orientation(a, b, d) < 0.0
+        && orientation(c, d, a) * orientation(c, d, b) < 0.0
+}
+
+fn distance_to_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx * dx + dy * dy;
+    let t = if length > 0.0 {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    ((a.0 + t * dx - p.0).powi(2) + (a.1 + t * dy - p.1).powi(2)).sqrt()
+}
+
+// `points` are indexed like the concepts, `edges` join concept indices.
+pub fn metrics(points: &[(f64, f64)], edges: &[(usize, usize)], radius: f64) -> Metrics {
+    let mut crossings = 0;
+    for (n, &(a, b)) in edges.iter().enumerate() {
+        for &(c, d) in &edges[(n + 1)..] {
+            if a != c
+                && a != d
+                && b != c
+                && b != d
+                && crosses(points[a], points[b], points[c], points[d])
+            {
+                crossings += 1;
+            }
+        }
+    }
+
+    let lengths: Vec<f64> = edges
+        .iter()
+        .map(|&(a, b)| {
+            ((points[a].0 - points[b].0).powi(2) + (points[a].1 - points[b].1).powi(2)).sqrt()
+        })
+        .collect();
+    let length_variance = if lengths.is_empty() {
+        0.0
+    } else {
+        let mean = lengths.iter().sum::<f64>() / lengths.len() as f64;
+        lengths.iter().map(|l| (l - mean).powi(2)).sum::<f64>() / lengths.len() as f64
+    };
+
+    let mut overlaps = 0;
+    for (n, p) in points.iter().enumerate() {
+        for q in &points[(n + 1)..] {
+            if ((p.0 - q.0).powi(2) + (p.1 - q.1).powi(2)).sqrt() < 2.0 * radius {
+                overlaps += 1;
+            }
+        }
+    }
+
+    let mut conflicts = 0;
+    for (n, &p) in points.iter().enumerate() {
+        for &(a, b) in edges {
+            if n != a && n != b && distance_to_segment(p, points[a], points[b]) < radius {
+                conflicts += 1;
+            }
+        }
+    }
+
+    Metrics {
+        crossings,
+        length_variance,
+        overlaps,
+        conflicts,
+    }
+}
+
+// Lower is better, the defects first and the edge lengths only to break ties.
+fn cost(
+    intents: &[BitSet],
+    levels: &[usize],
+    edges: &[(usize, usize)],
+    vectors: &[f64],
+) -> (usize, f64) {
+    let points: Vec<(f64, f64)> = additive(intents, vectors)
+        .into_iter()
+        .zip(levels)
+        .map(|(x, &level)| (x, level as f64))
+        .collect();
+    let metrics = metrics(&points, edges, 0.2);
+    (
+        metrics.crossings + metrics.overlaps + metrics.conflicts,
+        metrics.length_variance,
+    )
+}
+
+// Hill climbing over the attribute vectors of the additive layout. The levels stay
+// fixed, so the vertical order of the lattice is kept.
+pub fn optimise(
+    intents: &[BitSet],
+    edges: &[(usize, usize)],
+    vectors: &[f64],
+    rounds: usize,
+) -> Vec<f64> {
+    let levels = levels(intents, edges);
+    let mut vectors = vectors.to_vec();
+    let mut best = cost(intents, &levels, edges, &vectors);
+
+    for _ in 0..rounds {
+        let mut improved = false;
+        for m in 0..vectors.len() {
+            for step in STEPS {
+                vectors[m] += step;
+                let candidate = cost(intents, &levels, edges, &vectors);
+                if candidate < best {
+                    best = candidate;
+                    improved = true;
+                } else {
+                    vectors[m] -= step;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+    vectors
+}
@@ -0,0 +1,171 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::fca::{
+    derivation::{attribute_derivation, object_derivation},
+    generators::Rng,
+};
+
+// up to this many elements stability is computed over all subsets, above it is estimated
+pub const EXACT_LIMIT: usize = 14;
+pub const SAMPLES: usize = 500;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Measures {
+    pub support: f64,
+    pub intensional_stability: f64,
+    pub extensional_stability: f64,
+    pub separation: f64,
+    // probability of the intent if all attributes were independent
+    pub probability: f64,
+    pub lift: f64,
+    pub estimated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Measure {
+    Support,
+    IntensionalStability,
+    ExtensionalStability,
+    Separation,
+    Probability,
+    Lift,
+}
+
+impl Measure {
+    pub const ALL: [Measure; 6] = [
+        Measure::Support,
+        Measure::IntensionalStability,
+        Measure::ExtensionalStability,
+        Measure::Separation,
+        Measure::Probability,
+        Measure::Lift,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Measure::Support => "Support",
+            Measure::IntensionalStability => "Intensional stability",
+            Measure::ExtensionalStability => "Extensional stability",
+            Measure::Separation => "Separation",
+            Measure::Probability => "Probability",
+            Measure::Lift => "Lift",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Measure> {
+        Measure::ALL.into_iter().find(|n| n.name() == name)
+    }
+
+    pub fn value(&self, measures: &Measures) -> f64 {
+        match self {
+            Measure::Support => measures.support,
+            Measure::IntensionalStability => measures.intensional_stability,
+            Measure::ExtensionalStability => measures.extensional_stability,
+            Measure::Separation => measures.separation,
+            Measure::Probability => measures.probability,
+            Measure::Lift => measures.lift,
+        }
+    }
+}
+
+// Share of the subsets of `set` whose derivation equals `target`.
+fn stability(
+    set: &BitSet,
+    target: &BitSet,
+    derive: impl Fn(&BitSet) -> BitSet,
+    rng: &mut Rng,
+) -> (f64, bool) {
+    let elements: Vec<usize> = set.iter().collect();
+
+    if elements.len() <= EXACT_LIMIT {
+        let hits = (0..1u32 << elements.len())
+            .filter(|mask| {
+                let subset: BitSet = (0..elements.len())
+                    .filter(|n| mask & (1 << n) != 0)
+                    .map(|n| elements[n])
+                    .collect();
+                derive(&subset) == *target
+            })
+            .count();
+        (hits as f64 / (1u64 << elements.len()) as f64, false)
+    } else {
+        let hits = (0..SAMPLES)
+            .filter(|_| {
+                let subset: BitSet = elements
+                    .iter()
+                    .copied()
+                    .filter(|_| rng.next_u64() & 1 == 1)
+                    .collect();
+                derive(&subset) == *target
+            })
+            .count();
+        (hits as f64 / SAMPLES as f64, true)
+    }
+}
+
+pub fn measures(
+    context: &FormalContext<String>,
+    extent: &BitSet,
+    intent: &BitSet,
+    rng: &mut Rng,
+) -> Measures {
+    let objects = context.objects.len().max(1) as f64;
+
+    let (intensional_stability, estimated_i) =
+        stability(extent, intent, |set| object_derivation(context, set), rng);
+    let (extensional_stability, estimated_e) = stability(
+        intent,
+        extent,
+        |set| attribute_derivation(context, set),
+        rng,
+    );
+
+    let area = (extent.len() * intent.len()) as f64;
+    let covered: usize = extent
+        .iter()
+        .map(|g| context.atomic_object_derivations[g].len())
+        .chain(
+            intent
+                .iter()
+                .map(|m| context.atomic_attribute_derivations[m].len()),
+        )
+        .sum();
+    let separation = if covered as f64 - area > 0.0 {
+        area / (covered as f64 - area)
+    } else {
+        0.0
+    };
+
+    let support = extent.len() as f64 / objects;
+    let probability: f64 = intent
+        .iter()
+        .map(|m| context.atomic_attribute_derivations[m].len() as f64 / objects)
+        .product();
+    let lift = if probability > 0.0 {
+        support / probability
+    } else {
+        0.0
+    };
+
+    Measures {
+        support,
+        intensional_stability,
+        extensional_stability,
+        separation,
+        probability,
+        lift,
+        estimated: estimated_i || estimated_e,
+    }
+}
+
+pub fn all_measures(
+    context: &FormalContext<String>,
+    concepts: &[(BitSet, BitSet)],
+) -> Vec<Measures> {
+    let mut rng = Rng::new(0);
+    concepts
+        .iter()
+        .map(|(extent, intent)| measures(context, extent, intent, &mut rng))
+        .collect()
+}
@@ -0,0 +1,90 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+use std::collections::HashSet;
+
+// The context in which every unknown incidence is assumed to hold.
+pub fn plus_context(
+    context: &FormalContext<String>,
+    unknown: &HashSet<(usize, usize)>,
+) -> FormalContext<String> {
+    let mut plus = context.clone();
+    for &(g, m) in unknown {
+        plus.incidence.insert((g, m));
+        plus.atomic_object_derivations[g].insert(m);
+        plus.atomic_attribute_derivations[m].insert(g);
+    }
+    plus
+}
+
+// The attributes of object `g` that are unknown.
+pub fn unknown_attributes(unknown: &HashSet<(usize, usize)>, g: usize) -> BitSet {
+    unknown
+        .iter()
+        .filter(|(h, _)| *h == g)
+        .map(|(_, m)| *m)
+        .collect()
+}
+
+// Whether filling in the unknowns could make some object a counterexample.
+pub fn possibly_refuted(
+    context: &FormalContext<String>,
+    unknown: &HashSet<(usize, usize)>,
+    premise: &BitSet,
+    conclusion: &BitSet,
+) -> bool {
+    (0..context.objects.len()).any(|g| {
+        let certain = &context.atomic_object_derivations[g];
+        let mut possible = certain.clone();
+        possible.union_with(&unknown_attributes(unknown, g));
+        // the unknowns of the premise are assumed to hold, so only the rest of the
+        // conclusion can fail
+        premise.is_subset(&possible)
+            && !conclusion
+                .difference(premise)
+                .collect::<BitSet>()
+                .is_subset(certain)
+    })
+}
+
+pub fn transpose_unknown(unknown: &HashSet<(usize, usize)>) -> HashSet<(usize, usize)> {
+    unknown.iter().map(|&(g, m)| (m, g)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(elements: &[usize]) -> BitSet {
+        elements.iter().copied().collect()
+    }
+
+    // one object with the certain attribute v and the unknown attribute u
+    fn context() -> (FormalContext<String>, HashSet<(usize, usize)>) {
+        let context =
+            FormalContext::<String>::from("B\n\n1\n3\n\ng\nu\nv\nw\n.X.\n".as_bytes()).unwrap();
+        (context, HashSet::from([(0, 0)]))
+    }
+
+    #[test]
+    fn premise_attributes_in_the_conclusion_cannot_fail() {
+        let (context, unknown) = context();
+        assert!(!possibly_refuted(
+            &context,
+            &unknown,
+            &set(&[0]),
+            &set(&[0, 1])
+        ));
+    }
+
+    #[test]
+    fn unknown_premises_can_refute() {
+        let (context, unknown) = context();
+        assert!(possibly_refuted(&context, &unknown, &set(&[0]), &set(&[2])));
+        assert!(!possibly_refuted(
+            &context,
+            &unknown,
+            &set(&[2]),
+            &set(&[0])
+        ));
+    }
+}
@@ -0,0 +1,305 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::fca::{
+    derivation::{decode_set, encode_set},
+    exploration::Answer,
+};
+
+const HEADER: &str = "odis-web exploration session 2";
+// sessions without the attributes of the counterexamples in the history
+const HEADER_1: &str = "odis-web exploration session";
+
+// Everything needed to continue an attribute exploration at the same question.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub background: Vec<(BitSet, BitSet)>,
+    pub basis: Vec<(BitSet, BitSet)>,
+    // the set the next question is asked about
+    pub candidate: BitSet,
+    pub history: Vec<Answer>,
+    // object exploration on the transposed context
+    pub dual: bool,
+    // (object, attribute) incidences that are not known
+    pub unknown: Vec<(usize, usize)>,
+    // the context in Burmeister format
+    pub context: String,
+}
+
+fn decode_pair(line: &str) -> Result<(BitSet, BitSet), String> {
+    let (first, second) = line
+        .split_once(';')
+        .ok_or(format!("Invalid implication: {}", line))?;
+    Ok((decode_set(first)?, decode_set(second)?))
+}
+
+fn decode_incidence(line: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("Invalid incidence: {}", line);
+    let (g, m) = line.split_once(' ').ok_or_else(invalid)?;
+    Ok((
+        g.parse().map_err(|_| invalid())?,
+        m.parse().map_err(|_| invalid())?,
+    ))
+}
+
+// `premise;conclusion;basis_len;num_objects;num_attributes;time;intent;unknown;name`, the
+// name may contain `;` and is empty for accepted implications. Version 1 lacks
+// num_attributes, intent and unknown.
+fn decode_answer(line: &str, version: usize) -> Result<Answer, String> {
+    let invalid = || format!("Invalid answer: {}", line);
+    let count = if version == 1 { 6 } else { 9 };
+    let fields: Vec<&str> = line.splitn(count, ';').collect();
+    if fields.len() != count {
+        return Err(invalid());
+    }
+    let name = fields[count - 1];
+    let (num_attributes, time, attributes) = if version == 1 {
+        (None, fields[4], None)
+    } else {
+        (Some(fields[4]), fields[5], Some((fields[6], fields[7])))
+    };
+
+    Ok(Answer {
+        premise: decode_set(fields[0])?,
+        conclusion: decode_set(fields[1])?,
+        counterexample: (!name.is_empty()).then(|| name.to_string()),
+        counterexample_attributes: match attributes {
+            Some((intent, unknown)) if !name.is_empty() => {
+                Some((decode_set(intent)?, decode_set(unknown)?))
+            }
+            _ => None,
+        },
+        basis_len: fields[2].parse().map_err(|_| invalid())?,
+        num_objects: fields[3].parse().map_err(|_| invalid())?,
+        num_attributes: match num_attributes {
+            Some(n) if !n.is_empty() => Some(n.parse().map_err(|_| invalid())?),
+            _ => None,
+        },
+        time: time.parse().map_err(|_| invalid())?,
+    })
+}
+
+// Line based sections, the context comes last since it spans several lines itself.
+pub fn encode(session: &Session) -> String {
+    let mut text = format!(
+        "{}\nmode\n{}\nbackground\n",
+        HEADER,
+        if session.dual {
+            "objects"
+        } else {
+            "attributes"
+        }
+    );
+    for (premise, conclusion) in &session.background {
+        text.push_str(&format!(
+            "{};{}\n",
+            encode_set(premise),
+            encode_set(conclusion)
+        ));
+    }
+    text.push_str("basis\n");
+    for (premise, conclusion) in &session.basis {
+        text.push_str(&format!(
+            "{};{}\n",
+            encode_set(premise),
+            encode_set(conclusion)
+        ));
+    }
+    text.push_str(&format!(
+        "candidate\n{}\nhistory\n",
+        encode_set(&session.candidate)
+    ));
+    for answer in &session.history {
+        let (intent, unknown) = answer.counterexample_attributes.clone().unwrap_or_default();
+        text.push_str(&format!(
+            "{};{};{};{};{};{};{};{};{}\n",
+            encode_set(&answer.premise),
+            encode_set(&answer.conclusion),
+            answer.basis_len,
+            answer.num_objects,
+            answer
+                .num_attributes
+                .map_or(String::new(), |n| n.to_string()),
+            answer.time,
+            encode_set(&intent),
+            encode_set(&unknown),
+            answer.counterexample.clone().unwrap_or_default(),
+        ));
+    }
+    text.push_str("unknown\n");
+    for (g, m) in &session.unknown {
+        text.push_str(&format!("{} {}\n", g, m));
+    }
+    text.push_str("context\n");
+    text.push_str(&session.context);
+    text
+}
+
+pub fn decode(text: &str) -> Result<Session, String> {
+    let (head, context) = text
+        .split_once("\ncontext\n")
+        .ok_or("The session contains no context.")?;
+    let mut lines = head.lines();
+    let version = match lines.next().map(str::trim) {
+        Some(HEADER) => 2,
+        Some(HEADER_1) => 1,
+        _ => return Err("Not an exploration session.".to_string()),
+    };
+
+    let mut session = Session {
+        background: Vec::new(),
+        basis: Vec::new(),
+        candidate: BitSet::new(),
+        history: Vec::new(),
+        dual: false,
+        unknown: Vec::new(),
+        context: context.to_string(),
+    };
+    let mut section = "";
+
+    while let Some(line) = lines.next() {
+        match line {
+            "background" | "basis" | "history" | "unknown" => section = line,
+            "mode" => session.dual = lines.next() == Some("objects"),
+            "candidate" => session.candidate = decode_set(lines.next().unwrap_or(""))?,
+            _ => match section {
+                "background" => session.background.push(decode_pair(line)?),
+                "basis" => session.basis.push(decode_pair(line)?),
+                "history" => session.history.push(decode_answer(line, version)?),
+                "unknown" => session.unknown.push(decode_incidence(line)?),
+                _ => return Err(format!("Unexpected line: {}", line)),
+            },
+        }
+    }
+    Ok(session)
+}
+
+// Whether all indices of the session fit its parsed `context`, the implications are on the
+// objects in object exploration.
+pub fn check(session: &Session, context: &FormalContext<String>) -> Result<(), String> {
+    let explored = if session.dual {
+        context.objects.len()
+    } else {
+        context.attributes.len()
+    };
+    let mut sets = vec![&session.candidate];
+    for (premise, conclusion) in session.background.iter().chain(&session.basis) {
+        sets.extend([premise, conclusion]);
+    }
+    for answer in &session.history {
+        sets.extend([&answer.premise, &answer.conclusion]);
+        if let Some((intent, unknown)) = &answer.counterexample_attributes {
+            sets.extend([intent, unknown]);
+        }
+    }
+
+    if let Some(n) = sets
+        .iter()
+        .flat_map(|set| set.iter())
+        .find(|&n| n >= explored)
+    {
+        return Err(format!("The session refers to the missing index {}.", n));
+    }
+    if let Some((g, m)) = session
+        .unknown
+        .iter()
+        .find(|&&(g, m)| g >= context.objects.len() || m >= context.attributes.len())
+    {
+        return Err(format!(
+            "The session refers to the missing incidence {} {}.",
+            g, m
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(elements: &[usize]) -> BitSet {
+        elements.iter().copied().collect()
+    }
+
+    #[test]
+    fn decode_inverts_encode() {
+        let session = Session {
+            background: vec![(set(&[0]), set(&[0, 1]))],
+            basis: vec![(set(&[]), set(&[2])), (set(&[1, 2]), set(&[0, 1, 2]))],
+            candidate: set(&[1]),
+            history: vec![
+                Answer {
+                    premise: set(&[]),
+                    conclusion: set(&[2]),
+                    counterexample: None,
+                    counterexample_attributes: None,
+                    basis_len: 1,
+                    num_objects: 2,
+                    num_attributes: Some(3),
+                    time: 1.5,
+                },
+                Answer {
+                    premise: set(&[1]),
+                    conclusion: set(&[0, 1]),
+                    counterexample: Some("cat; small".to_string()),
+                    counterexample_attributes: Some((set(&[0, 1]), set(&[2]))),
+                    basis_len: 1,
+                    num_objects: 3,
+                    num_attributes: None,
+                    time: 4.25,
+                },
+            ],
+            dual: true,
+            unknown: vec![(0, 2), (1, 0)],
+            context: "B\n\n2\n3\n\na\nb\nx\ny\nz\n.X.\nXX.\n".to_string(),
+        };
+
+        assert_eq!(decode(&encode(&session)), Ok(session));
+    }
+
+    #[test]
+    fn version_1_sessions_are_read() {
+        let text = format!(
+            "{}\nhistory\n;2;0;2;1.5;\n1;0 1;1;3;4.25;cat; small\ncontext\nB\n",
+            HEADER_1
+        );
+        let history = decode(&text).unwrap().history;
+
+        assert_eq!(history[0].counterexample, None);
+        assert_eq!(history[1].counterexample, Some("cat; small".to_string()));
+        assert_eq!(history[1].counterexample_attributes, None);
+        assert_eq!(history[1].num_attributes, None);
+        assert_eq!(history[1].time, 4.25);
+    }
+
+    #[test]
+    fn check_rejects_missing_indices() {
+        let context =
+            FormalContext::<String>::from("B\n\n2\n2\n\na\nb\nx\ny\nX.\n.X\n".as_bytes()).unwrap();
+        let mut session = decode(&format!(
+            "{}\ncandidate\n1\nunknown\n1 1\ncontext\nB\n",
+            HEADER
+        ))
+        .unwrap();
+        assert_eq!(check(&session, &context), Ok(()));
+
+        session.basis.push((set(&[0]), set(&[0, 2])));
+        assert_eq!(
+            check(&session, &context),
+            Err("The session refers to the missing index 2.".to_string())
+        );
+
+        session.basis.clear();
+        session.unknown.push((2, 0));
+        assert_eq!(
+            check(&session, &context),
+            Err("The session refers to the missing incidence 2 0.".to_string())
+        );
+    }
+
+    #[test]
+    fn decode_rejects_invalid_sets() {
+        let text = format!("{}\ncandidate\n1 x\ncontext\nB\n", HEADER);
+        assert_eq!(decode(&text), Err("Invalid index: x".to_string()));
+    }
+}
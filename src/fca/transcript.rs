@@ -0,0 +1,229 @@
+use bit_set::BitSet;
+use odis::FormalContext;
+
+use crate::fca::{
+    exploration::{Exploration, State},
+    implications::format_implication,
+};
+
+// "2024-05-01 13:45:09 UTC" for milliseconds since the epoch
+pub fn format_time(time: f64) -> String {
+    let seconds = (time / 1000.0).floor() as i64;
+    let (days, seconds) = (seconds.div_euclid(86400), seconds.rem_euclid(86400));
+
+    // civil date from days since 1970-01-01, after Howard Hinnant
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+// attributes removed since are left out
+fn names(set: &BitSet, names: &[String]) -> Vec<String> {
+    set.iter().filter_map(|n| names.get(n).cloned()).collect()
+}
+
+fn status(exploration: &Exploration, attributes: &[String]) -> String {
+    match &exploration.state {
+        State::Finished => "finished".to_string(),
+        State::AskImplication {
+            premise,
+            conclusion,
+        }
+        | State::AwaitCounterexample {
+            premise,
+            conclusion,
+        } => format!(
+            "in progress, open question: {}",
+            format_implication(&(premise.clone(), conclusion.clone()), attributes)
+        ),
+    }
+}
+
+// `context` is the explored one, transposed for object exploration.
+pub fn markdown(
+    exploration: &Exploration,
+    context: &FormalContext<String>,
+    dual: bool,
+    exported: f64,
+) -> String {
+    let attributes = &context.attributes;
+    let (kind, items) = if dual {
+        ("Object", "objects")
+    } else {
+        ("Attribute", "attributes")
+    };
+    let list = |implications: &[(BitSet, BitSet)]| {
+        if implications.is_empty() {
+            "none\n".to_string()
+        } else {
+            implications
+                .iter()
+                .map(|implication| format!("- `{}`\n", format_implication(implication, attributes)))
+                .collect()
+        }
+    };
+
+    let mut text = format!("# {} exploration transcript\n\n", kind);
+    text.push_str(&format!("- Exported: {}\n", format_time(exported)));
+    text.push_str(&format!("- Status: {}\n", status(exploration, attributes)));
+    text.push_str(&format!(
+        "- Explored {}: {}\n",
+        items,
+        attributes.join(", ")
+    ));
+
+    text.push_str("\n## Background knowledge\n\n");
+    text.push_str(&list(&exploration.background));
+
+    text.push_str("\n## Questions\n\n");
+    if exploration.history.is_empty() {
+        text.push_str("none\n");
+    }
+    for (n, answer) in exploration.history.iter().enumerate() {
+        let implication = format_implication(
+            &(answer.premise.clone(), answer.conclusion.clone()),
+            attributes,
+        );
+        text.push_str(&format!(
+            "{}. `{}` at {}: ",
+            n + 1,
+            implication,
+            format_time(answer.time)
+        ));
+        match &answer.counterexample {
+            None => text.push_str("accepted\n"),
+            Some(name) => match &answer.counterexample_attributes {
+                Some((intent, unknown)) => text.push_str(&format!(
+                    "rejected, counterexample **{}** with {{{}}}, unknown {{{}}}\n",
+                    name,
+                    names(intent, attributes).join(", "),
+                    names(unknown, attributes).join(", "),
+                )),
+                None => text.push_str(&format!("rejected, counterexample **{}**\n", name)),
+            },
+        }
+    }
+
+    text.push_str("\n## Confirmed implications\n\n");
+    text.push_str(&list(&exploration.basis));
+    text
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_names(set: &BitSet, attributes: &[String]) -> String {
+    let names: Vec<String> = names(set, attributes)
+        .iter()
+        .map(|name| json_string(name))
+        .collect();
+    format!("[{}]", names.join(", "))
+}
+
+fn json_implications(implications: &[(BitSet, BitSet)], attributes: &[String]) -> String {
+    let implications: Vec<String> = implications
+        .iter()
+        .map(|(premise, conclusion)| {
+            format!(
+                "\n    {{\"premise\": {}, \"conclusion\": {}}}",
+                json_names(premise, attributes),
+                json_names(&conclusion.difference(premise).collect(), attributes),
+            )
+        })
+        .collect();
+    if implications.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[{}\n  ]", implications.join(","))
+    }
+}
+
+pub fn json(
+    exploration: &Exploration,
+    context: &FormalContext<String>,
+    dual: bool,
+    exported: f64,
+) -> String {
+    let attributes = &context.attributes;
+
+    let questions: Vec<String> = exploration
+        .history
+        .iter()
+        .map(|answer| {
+            let answer_text = match &answer.counterexample {
+                None => "\"answer\": \"accepted\"".to_string(),
+                Some(name) => {
+                    let details = match &answer.counterexample_attributes {
+                        Some((intent, unknown)) => format!(
+                            ", \"attributes\": {}, \"unknown\": {}",
+                            json_names(intent, attributes),
+                            json_names(unknown, attributes),
+                        ),
+                        None => String::new(),
+                    };
+                    format!(
+                        "\"answer\": \"rejected\", \"counterexample\": {{\"name\": {}{}}}",
+                        json_string(name),
+                        details
+                    )
+                }
+            };
+            format!(
+                "\n    {{\"premise\": {}, \"conclusion\": {}, \"time\": {}, {}}}",
+                json_names(&answer.premise, attributes),
+                json_names(
+                    &answer.conclusion.difference(&answer.premise).collect(),
+                    attributes
+                ),
+                json_string(&format_time(answer.time)),
+                answer_text,
+            )
+        })
+        .collect();
+
+    let all: BitSet = (0..attributes.len()).collect();
+    format!(
+        "{{\n  \"mode\": {},\n  \"exported\": {},\n  \"status\": {},\n  \"explored\": {},\n  \"background\": {},\n  \"questions\": {},\n  \"basis\": {}\n}}\n",
+        json_string(if dual { "objects" } else { "attributes" }),
+        json_string(&format_time(exported)),
+        json_string(&status(exploration, attributes)),
+        json_names(&all, attributes),
+        json_implications(&exploration.background, attributes),
+        if questions.is_empty() {
+            "[]".to_string()
+        } else {
+            format!("[{}\n  ]", questions.join(","))
+        },
+        json_implications(&exploration.basis, attributes),
+    )
+}
@@ -1,5 +1,8 @@
 use wasm_bindgen_futures::JsFuture;
-use web_sys::File;
+use web_sys::{
+    Blob, BlobPropertyBag, File, HtmlAnchorElement, MessageEvent, Storage, Url, Worker,
+    wasm_bindgen::{JsCast, JsValue, closure::Closure},
+};
 
 pub async fn file_contents(file: File) -> String {
     JsFuture::from(file.text())
@@ -8,3 +11,59 @@ pub async fn file_contents(file: File) -> String {
         .as_string()
         .unwrap()
 }
+
+// milliseconds since the epoch
+pub fn timestamp() -> f64 {
+    let performance = web_sys::window().unwrap().performance().unwrap();
+    performance.time_origin() + performance.now()
+}
+
+// Starts the computation worker and sends it `message`. The closure has to be kept alive
+// as long as answers are expected.
+pub fn start_worker(
+    message: String,
+    mut on_message: impl FnMut(String) + 'static,
+) -> (Worker, Closure<dyn FnMut(MessageEvent)>) {
+    let worker = Worker::new("./worker_loader.js").unwrap();
+    let closure = Closure::<dyn FnMut(MessageEvent)>::new(move |ev: MessageEvent| {
+        if let Some(text) = ev.data().as_string() {
+            on_message(text);
+        }
+    });
+    worker.set_onmessage(Some(closure.as_ref().unchecked_ref()));
+    worker.post_message(&JsValue::from(message)).unwrap();
+    (worker, closure)
+}
+
+// Offers `content` as a file download through the hidden link `link`.
+pub fn download(link: &HtmlAnchorElement, name: &str, content: String, mime: &str) {
+    let property_bag = BlobPropertyBag::new();
+    property_bag.set_type(mime);
+    let blob =
+        Blob::new_with_str_sequence_and_options(&JsValue::from(vec![content]), &property_bag)
+            .unwrap();
+    let url = Url::create_object_url_with_blob(&blob).unwrap();
+
+    link.set_download(name);
+    link.set_href(&url);
+    link.click();
+}
+
+// Storage may be disabled, e.g. in private windows.
+fn local_storage() -> Result<Storage, String> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or("The browser storage is not available.".to_string())
+}
+
+pub fn store(key: &str, value: &str) -> Result<(), String> {
+    local_storage()?
+        .set_item(key, value)
+        .map_err(|_| "The browser storage is full.".to_string())
+}
+
+pub fn load(key: &str) -> Result<Option<String>, String> {
+    local_storage()?
+        .get_item(key)
+        .map_err(|_| "The browser storage could not be read.".to_string())
+}
@@ -7,10 +7,20 @@ use odis::{self, FormalContext};
 use crate::components::table::TableComp;
 
 mod components {
+    pub mod algorithms;
+    pub mod association;
+    pub mod bases;
     pub mod checkbox;
+    pub mod closed_sets;
+    pub mod combine;
+    pub mod concept_list;
+    pub mod derivation;
     pub mod download;
     pub mod exploration;
+    pub mod generator;
     pub mod graph;
+    pub mod implication;
+    pub mod subcontext;
     pub mod svg_download;
     pub mod table;
     pub mod svg {
@@ -19,7 +29,25 @@ mod components {
     }
 }
 
+mod fca {
+    pub mod association;
+    pub mod bases;
+    pub mod closed_sets;
+    pub mod combine;
+    pub mod concepts;
+    pub mod derivation;
+    pub mod exploration;
+    pub mod generators;
+    pub mod implications;
+    pub mod layout;
+    pub mod measures;
+    pub mod partial;
+    pub mod session;
+    pub mod transcript;
+}
+
 mod js_fn;
+mod worker_protocol;
 
 #[component]
 pub fn App() -> impl IntoView {
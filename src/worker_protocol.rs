@@ -0,0 +1,191 @@
+// Messages between the page and the worker binary. Both binaries include this file,
+// each only uses one direction of it.
+#![allow(dead_code)]
+
+use bit_set::BitSet;
+
+use crate::fca::derivation::{decode_set, encode_set};
+
+pub const BATCH_SIZE: usize = 200;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Job {
+    // name of the enumeration algorithm
+    Concepts(String),
+    Basis,
+    // all enumeration algorithms, timed
+    Compare,
+    // the concepts as for `Concepts`, then the diagram of their lattice
+    Lattice(String),
+}
+
+// object and attribute label of a node
+pub type Labels = (Option<String>, Option<String>);
+
+// The drawn lattice, nodes as (concept, x, y, labels) with the concepts in lectic order.
+// Empty if the lattice cannot be drawn.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diagram {
+    pub nodes: Vec<(usize, f64, f64, Labels)>,
+    pub edges: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Concepts(Vec<(BitSet, BitSet)>),
+    Implications(Vec<(BitSet, BitSet)>),
+    // algorithm name, number of concepts, milliseconds, same concepts as the first algorithm
+    Timings(Vec<(String, usize, f64, bool)>),
+    Diagram(Diagram),
+    Done,
+    Error(String),
+}
+
+// Labels may contain any text, `;` and line breaks are escaped. `None` is the empty field,
+// every label starts with `+`.
+fn encode_label(label: &Option<String>) -> String {
+    match label {
+        Some(text) => format!(
+            "+{}",
+            text.replace('\\', "\\\\")
+                .replace('\n', "\\n")
+                .replace(';', "\\s")
+        ),
+        None => String::new(),
+    }
+}
+
+fn decode_label(field: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut chars = field.strip_prefix('+')?.chars();
+    while let Some(c) = chars.next() {
+        text.push(if c == '\\' {
+            match chars.next() {
+                Some('n') => '\n',
+                Some('s') => ';',
+                _ => '\\',
+            }
+        } else {
+            c
+        });
+    }
+    Some(text)
+}
+
+fn encode_pairs(kind: &str, pairs: &[(BitSet, BitSet)]) -> String {
+    let mut text = kind.to_string();
+    for (first, second) in pairs {
+        text.push_str(&format!("\n{};{}", encode_set(first), encode_set(second)));
+    }
+    text
+}
+
+fn decode_pairs<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<(BitSet, BitSet)> {
+    lines
+        .filter_map(|line| line.split_once(';'))
+        .filter_map(|(first, second)| Some((decode_set(first).ok()?, decode_set(second).ok()?)))
+        .collect()
+}
+
+// the job on the first line, the context in Burmeister format below
+pub fn encode_job(job: &Job, context: &str) -> String {
+    match job {
+        Job::Concepts(algorithm) => format!("concepts {}\n{}", algorithm, context),
+        Job::Basis => format!("basis\n{}", context),
+        Job::Compare => format!("compare\n{}", context),
+        Job::Lattice(algorithm) => format!("lattice {}\n{}", algorithm, context),
+    }
+}
+
+pub fn decode_job(text: &str) -> Option<(Job, &str)> {
+    let (first, context) = text.split_once('\n')?;
+    if first == "basis" {
+        Some((Job::Basis, context))
+    } else if first == "compare" {
+        Some((Job::Compare, context))
+    } else if let Some(algorithm) = first.strip_prefix("lattice ") {
+        Some((Job::Lattice(algorithm.to_string()), context))
+    } else {
+        let algorithm = first.strip_prefix("concepts ")?;
+        Some((Job::Concepts(algorithm.to_string()), context))
+    }
+}
+
+pub fn encode_message(message: &Message) -> String {
+    match message {
+        Message::Concepts(concepts) => encode_pairs("concepts", concepts),
+        Message::Implications(implications) => encode_pairs("implications", implications),
+        Message::Timings(timings) => {
+            let mut text = "timings".to_string();
+            for (name, concepts, milliseconds, agrees) in timings {
+                text.push_str(&format!(
+                    "\n{};{};{};{}",
+                    name, concepts, milliseconds, agrees
+                ));
+            }
+            text
+        }
+        Message::Diagram(diagram) => {
+            let mut text = "diagram".to_string();
+            for (n, x, y, (first, second)) in &diagram.nodes {
+                text.push_str(&format!(
+                    "\nnode;{};{};{};{};{}",
+                    n,
+                    x,
+                    y,
+                    encode_label(first),
+                    encode_label(second)
+                ));
+            }
+            for (start, end) in &diagram.edges {
+                text.push_str(&format!("\nedge;{};{}", start, end));
+            }
+            text
+        }
+        Message::Done => "done".to_string(),
+        Message::Error(error) => format!("error\n{}", error),
+    }
+}
+
+pub fn decode_message(text: &str) -> Option<Message> {
+    let mut lines = text.lines();
+    match lines.next()? {
+        "concepts" => Some(Message::Concepts(decode_pairs(lines))),
+        "implications" => Some(Message::Implications(decode_pairs(lines))),
+        "timings" => Some(Message::Timings(
+            lines
+                .filter_map(|line| {
+                    let mut fields = line.split(';');
+                    Some((
+                        fields.next()?.to_string(),
+                        fields.next()?.parse().ok()?,
+                        fields.next()?.parse().ok()?,
+                        fields.next()?.parse().ok()?,
+                    ))
+                })
+                .collect(),
+        )),
+        "diagram" => {
+            let mut diagram = Diagram::default();
+            for line in lines {
+                let fields: Vec<&str> = line.split(';').collect();
+                match fields[..] {
+                    ["node", n, x, y, first, second] => diagram.nodes.push((
+                        n.parse().ok()?,
+                        x.parse().ok()?,
+                        y.parse().ok()?,
+                        (decode_label(first), decode_label(second)),
+                    )),
+                    ["edge", start, end] => {
+                        diagram.edges.push((start.parse().ok()?, end.parse().ok()?))
+                    }
+                    _ => return None,
+                }
+            }
+            Some(Message::Diagram(diagram))
+        }
+        "done" => Some(Message::Done),
+        "error" => Some(Message::Error(lines.collect::<Vec<&str>>().join("\n"))),
+        _ => None,
+    }
+}